@@ -0,0 +1,888 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use crate::db::{CpuMode, PauseReason};
+
+/// Where procfs is mounted, every source can instead be read from a different root
+pub const PROC_ROOT: &str = "/proc";
+
+/// `/proc/diskstats` counts in 512 byte sectors, regardless of the sector size of the disk
+const BYTES_PER_SECTOR: u64 = 512;
+
+const BYTES_PER_KB: f64 = 1024.0;
+
+/// A single measurement of every source, a source is `None` if it couldn't be read, or has nothing to compare against yet
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ActivitySample {
+    /// Percentage of cpu usage, measured as set by `cpu_mode`
+    pub cpu: Option<f32>,
+    pub cpu_mode: CpuMode,
+    /// KB/s received & transmitted, excluding the loopback interface
+    pub network_kb: Option<f32>,
+    /// KB/s read & written, excluding partitions, and virtual devices
+    pub disk_kb: Option<f32>,
+}
+
+/// A source of activity, sampled roughly once a second from the heartbeat
+pub trait ActivitySource: Send {
+    /// Measure the activity since the previous sample, `None` if the source couldn't be read, or on the first sample
+    fn sample(&mut self) -> Option<f32>;
+}
+
+/// Whether a signal, or a composite of signals, counts as active, and whether it counts as idle, over its timespan
+/// A signal without enough data is neither active nor idle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SignalState {
+    pub active: bool,
+    pub idle: bool,
+}
+
+impl SignalState {
+    /// Active at, or above, the active threshold, idle at, or below, the idle threshold
+    pub fn from_averages(
+        active_avg: Option<f32>,
+        active_threshold: f64,
+        idle_avg: Option<f32>,
+        idle_threshold: f64,
+    ) -> Self {
+        Self {
+            active: active_avg.is_some_and(|avg| f64::from(avg) >= active_threshold),
+            idle: idle_avg.is_some_and(|avg| f64::from(avg) <= idle_threshold),
+        }
+    }
+
+    /// Active if any signal is active, idle only once every signal is idle
+    pub fn any(states: &[Self]) -> Self {
+        Self {
+            active: states.iter().any(|i| i.active),
+            idle: !states.is_empty() && states.iter().all(|i| i.idle),
+        }
+    }
+
+    /// Active only once every signal is active, idle if any signal is idle
+    pub fn all(states: &[Self]) -> Self {
+        Self {
+            active: !states.is_empty() && states.iter().all(|i| i.active),
+            idle: states.iter().any(|i| i.idle),
+        }
+    }
+}
+
+/// How a window of samples is reduced to a single value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregator {
+    Mean,
+    Median,
+    /// Nearest-rank percentile, between 1 and 100
+    Percentile(u8),
+    /// Exponentially weighted moving average, each newer sample is weighted by alpha, between 0 and 1
+    Ewma(f32),
+}
+
+impl Aggregator {
+    /// Reduce samples, ordered from oldest to newest, `None` if there are no samples
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn aggregate(self, samples: &[f32]) -> Option<f32> {
+        if samples.is_empty() {
+            return None;
+        }
+        match self {
+            Self::Mean => Some(samples.iter().sum::<f32>() / samples.len() as f32),
+            Self::Median => {
+                let mut sorted = samples.to_vec();
+                sorted.sort_by(f32::total_cmp);
+                let middle = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) {
+                    Some(f32::midpoint(sorted[middle - 1], sorted[middle]))
+                } else {
+                    sorted.get(middle).copied()
+                }
+            }
+            Self::Percentile(percentile) => {
+                let mut sorted = samples.to_vec();
+                sorted.sort_by(f32::total_cmp);
+                let rank = (f64::from(percentile.clamp(1, 100)) / 100.0 * sorted.len() as f64)
+                    .ceil() as usize;
+                sorted.get(rank.saturating_sub(1)).copied()
+            }
+            Self::Ewma(alpha) => samples
+                .iter()
+                .copied()
+                .reduce(|avg, sample| alpha.mul_add(sample, (1.0 - alpha) * avg)),
+        }
+    }
+}
+
+/// An automatic change between paused & running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutoTransition {
+    Pause,
+    Resume,
+}
+
+/// Which of the automatic transitions are enabled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AutoEnabled {
+    pub pause: bool,
+    pub resume: bool,
+}
+
+/// Guards auto-pause & auto-resume against flapping near a threshold
+/// Times are in seconds, from any fixed point, so that a recorded trace can be replayed against it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TransitionGuard {
+    /// The previous auto transition, and when it happened
+    previous: Option<(AutoTransition, i64)>,
+}
+
+impl TransitionGuard {
+    /// The pause & resume thresholds, whichever would reverse the previous transition is moved away from it by `band`
+    pub fn get_thresholds(&self, pause: f64, resume: f64, band: f64) -> (f64, f64) {
+        match self.previous {
+            Some((AutoTransition::Pause, _)) => (pause, resume + band),
+            Some((AutoTransition::Resume, _)) => (pause - band, resume),
+            None => (pause, resume),
+        }
+    }
+
+    /// Resume an active signal whilst auto-paused, pause an idle signal whilst running, nothing is decided within `dwell_sec` of the previous transition
+    /// Only a pause caused by low activity is resumed by activity, `pause_reason` is `None` whilst running
+    pub fn decide(
+        &self,
+        now_sec: i64,
+        dwell_sec: i64,
+        pause_reason: Option<PauseReason>,
+        enabled: AutoEnabled,
+        state: SignalState,
+    ) -> Option<AutoTransition> {
+        if self
+            .previous
+            .is_some_and(|(_, at)| now_sec.saturating_sub(at) < dwell_sec)
+        {
+            return None;
+        }
+        match (pause_reason, state.active, state.idle) {
+            (Some(PauseReason::Cpu), true, _) if enabled.resume => Some(AutoTransition::Resume),
+            (None, _, true) if enabled.pause => Some(AutoTransition::Pause),
+            _ => None,
+        }
+    }
+
+    /// Record a transition that has been acted upon
+    pub const fn record(&mut self, transition: AutoTransition, now_sec: i64) {
+        self.previous = Some((transition, now_sec));
+    }
+}
+
+/// Turn an ever increasing byte counter into a KB/s rate
+#[derive(Debug, Clone, Copy, Default)]
+struct ByteRate {
+    previous: Option<(u64, Instant)>,
+}
+
+impl ByteRate {
+    /// A counter that has gone backwards, such as after an interface is removed, is treated as no activity
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn update(&mut self, bytes: u64) -> Option<f32> {
+        let (previous_bytes, previous_instant) = self.previous.replace((bytes, Instant::now()))?;
+        let elapsed = previous_instant.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        Some((bytes.saturating_sub(previous_bytes) as f64 / BYTES_PER_KB / elapsed) as f32)
+    }
+}
+
+/// Read the busy & total jiffies from a cpu line of `stat`, idle & iowait aren't busy, guest time is already included in user time
+fn parse_cpu_line(line: &str) -> Option<(u64, u64)> {
+    let values = line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|value| value.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let idle = values.get(3)? + values.get(4).copied().unwrap_or_default();
+    let total = values.iter().sum::<u64>();
+    Some((total.saturating_sub(idle), total))
+}
+
+/// Percentage of busy jiffies between two reads of a cpu line
+#[allow(clippy::cast_precision_loss)]
+fn calc_cpu_usage((previous_busy, previous_total): (u64, u64), (busy, total): (u64, u64)) -> f32 {
+    let total = total.saturating_sub(previous_total);
+    if total == 0 {
+        return 0.0;
+    }
+    busy.saturating_sub(previous_busy) as f32 * 100.0 / total as f32
+}
+
+/// Cpu usage from `stat`, either global, from the first line, or the busiest single core, from the per-core lines
+pub struct ProcCpu {
+    root: PathBuf,
+    per_core: bool,
+    /// Busy & total jiffies of every line read at the previous sample
+    previous: Vec<(u64, u64)>,
+}
+
+impl ProcCpu {
+    pub fn new(root: &Path, per_core: bool) -> Self {
+        Self {
+            root: root.to_owned(),
+            per_core,
+            previous: vec![],
+        }
+    }
+
+    /// Read the busy & total jiffies of all cpus, or of each core
+    fn read(&self) -> Option<Vec<(u64, u64)>> {
+        let stat = std::fs::read_to_string(self.root.join("stat")).ok()?;
+        let lines = stat
+            .lines()
+            .filter(|line| {
+                line.strip_prefix("cpu").is_some_and(|rest| {
+                    rest.starts_with(|c: char| c.is_ascii_digit()) == self.per_core
+                })
+            })
+            .map(parse_cpu_line)
+            .collect::<Option<Vec<_>>>()?;
+        (!lines.is_empty()).then_some(lines)
+    }
+}
+
+impl ActivitySource for ProcCpu {
+    /// A change in the number of cores, such as a cpu being taken offline, has nothing to compare against, so is skipped
+    fn sample(&mut self) -> Option<f32> {
+        let current = self.read()?;
+        let previous = std::mem::replace(&mut self.previous, current);
+        if previous.len() != self.previous.len() {
+            return None;
+        }
+        previous
+            .into_iter()
+            .zip(self.previous.iter().copied())
+            .map(|(previous, current)| calc_cpu_usage(previous, current))
+            .reduce(f32::max)
+    }
+}
+
+/// Cpu usage from sysinfo, either global, or the busiest single core, for platforms without a procfs
+pub struct SysinfoCpu {
+    sys: sysinfo::System,
+    per_core: bool,
+}
+
+impl SysinfoCpu {
+    pub fn new(per_core: bool) -> Self {
+        Self {
+            sys: sysinfo::System::new(),
+            per_core,
+        }
+    }
+}
+
+impl ActivitySource for SysinfoCpu {
+    fn sample(&mut self) -> Option<f32> {
+        self.sys.refresh_cpu_usage();
+        if self.per_core {
+            self.sys
+                .cpus()
+                .iter()
+                .map(sysinfo::Cpu::cpu_usage)
+                .reduce(f32::max)
+        } else {
+            Some(self.sys.global_cpu_usage())
+        }
+    }
+}
+
+/// Whether a process name matches a name chosen by the user, ignoring case, and any `.exe` extension
+fn matches_process_name(process: &str, name: &str) -> bool {
+    let process = process.to_lowercase();
+    let process = process.strip_suffix(".exe").unwrap_or(&process);
+    let name = name.trim().to_lowercase();
+    process == name.strip_suffix(".exe").unwrap_or(&name)
+}
+
+/// Summed cpu usage of every process matching a set of names, from sysinfo
+/// Each process is measured as a percentage of a single core, so the sum is capped at 100
+pub struct ProcessCpu {
+    sys: sysinfo::System,
+    names: Vec<String>,
+    /// sysinfo needs two refreshes to calculate the usage of a process
+    refreshed: bool,
+}
+
+impl ProcessCpu {
+    pub fn new(names: &[String]) -> Self {
+        Self {
+            sys: sysinfo::System::new(),
+            names: names.to_vec(),
+            refreshed: false,
+        }
+    }
+}
+
+impl ActivitySource for ProcessCpu {
+    fn sample(&mut self) -> Option<f32> {
+        self.sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::All,
+            true,
+            sysinfo::ProcessRefreshKind::nothing().with_cpu(),
+        );
+        if !std::mem::replace(&mut self.refreshed, true) {
+            return None;
+        }
+        let usage = self
+            .sys
+            .processes()
+            .values()
+            .filter(|process| {
+                let process = process.name().to_string_lossy();
+                self.names
+                    .iter()
+                    .any(|name| matches_process_name(&process, name))
+            })
+            .map(sysinfo::Process::cpu_usage)
+            .sum::<f32>();
+        Some(usage.min(100.0))
+    }
+}
+
+/// Network throughput, of every interface other than loopback, from `net/dev`
+pub struct ProcNetwork {
+    root: PathBuf,
+    rate: ByteRate,
+}
+
+impl ProcNetwork {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_owned(),
+            rate: ByteRate::default(),
+        }
+    }
+
+    /// Read the total bytes received & transmitted, the first two lines are headers
+    fn read(&self) -> Option<u64> {
+        let dev = std::fs::read_to_string(self.root.join("net").join("dev")).ok()?;
+        Some(
+            dev.lines()
+                .skip(2)
+                .filter_map(|line| line.split_once(':'))
+                .filter(|(interface, _)| interface.trim() != "lo")
+                .filter_map(|(_, counters)| {
+                    let counters = counters.split_whitespace().collect::<Vec<_>>();
+                    let received = counters.first()?.parse::<u64>().ok()?;
+                    let transmitted = counters.get(8)?.parse::<u64>().ok()?;
+                    Some(received + transmitted)
+                })
+                .sum(),
+        )
+    }
+}
+
+impl ActivitySource for ProcNetwork {
+    fn sample(&mut self) -> Option<f32> {
+        let bytes = self.read()?;
+        self.rate.update(bytes)
+    }
+}
+
+/// Disk throughput, of whole disks only, from `diskstats`
+pub struct ProcDisk {
+    root: PathBuf,
+    rate: ByteRate,
+}
+
+impl ProcDisk {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_owned(),
+            rate: ByteRate::default(),
+        }
+    }
+
+    /// Read the total bytes read & written, virtual devices are ignored, as are partitions, which are already counted by their disk
+    fn read(&self) -> Option<u64> {
+        let diskstats = std::fs::read_to_string(self.root.join("diskstats")).ok()?;
+        let devices = diskstats
+            .lines()
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<_>>();
+                let name = *fields.get(2)?;
+                let read = fields.get(5)?.parse::<u64>().ok()?;
+                let written = fields.get(9)?.parse::<u64>().ok()?;
+                Some((name, read + written))
+            })
+            .filter(|(name, _)| {
+                !["loop", "ram", "zram", "dm-"]
+                    .iter()
+                    .any(|i| name.starts_with(i))
+            })
+            .collect::<Vec<_>>();
+        Some(
+            devices
+                .iter()
+                .filter(|(name, _)| {
+                    !devices
+                        .iter()
+                        .any(|(disk, _)| disk != name && name.starts_with(disk))
+                })
+                .map(|(_, sectors)| sectors * BYTES_PER_SECTOR)
+                .sum(),
+        )
+    }
+}
+
+impl ActivitySource for ProcDisk {
+    fn sample(&mut self) -> Option<f32> {
+        let bytes = self.read()?;
+        self.rate.update(bytes)
+    }
+}
+
+/// Names of every running process, from sysinfo
+pub struct ProcessSampler {
+    sys: sysinfo::System,
+}
+
+impl ProcessSampler {
+    pub fn new() -> Self {
+        Self {
+            sys: sysinfo::System::new(),
+        }
+    }
+
+    /// Refresh the process list, and return the unique names of every process
+    pub fn sample(&mut self) -> Vec<String> {
+        self.sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::All,
+            true,
+            sysinfo::ProcessRefreshKind::nothing(),
+        );
+        let mut names = self
+            .sys
+            .processes()
+            .values()
+            .map(|process| process.name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+/// Every activity source, read from a single procfs root
+pub struct ActivitySampler {
+    cpu: Box<dyn ActivitySource>,
+    cpu_mode: CpuMode,
+    network: Box<dyn ActivitySource>,
+    disk: Box<dyn ActivitySource>,
+}
+
+impl ActivitySampler {
+    /// Read every source from the procfs at `root`, the cpu usage is read from sysinfo if `root` doesn't contain a `stat` file
+    /// `cpu_processes` are only used by `CpuMode::Processes`, which is always read from sysinfo
+    pub fn new(root: &Path, cpu_mode: CpuMode, cpu_processes: &[String]) -> Self {
+        let per_core = cpu_mode == CpuMode::MaxCore;
+        let cpu: Box<dyn ActivitySource> = if cpu_mode == CpuMode::Processes {
+            Box::new(ProcessCpu::new(cpu_processes))
+        } else if root.join("stat").exists() {
+            Box::new(ProcCpu::new(root, per_core))
+        } else {
+            Box::new(SysinfoCpu::new(per_core))
+        };
+        Self {
+            cpu,
+            cpu_mode,
+            network: Box::new(ProcNetwork::new(root)),
+            disk: Box::new(ProcDisk::new(root)),
+        }
+    }
+
+    pub fn sample(&mut self) -> ActivitySample {
+        ActivitySample {
+            cpu: self.cpu.sample(),
+            cpu_mode: self.cpu_mode,
+            network_kb: self.network.sample(),
+            disk_kb: self.disk.sample(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    /// A procfs root in the temp dir, removed once dropped
+    struct FakeProc(PathBuf);
+
+    impl FakeProc {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir()
+                .join(format!("obliqoro_activity_{name}_{}", std::process::id()));
+            std::fs::create_dir_all(root.join("net")).unwrap();
+            Self(root)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            std::fs::write(self.0.join(file), contents).unwrap();
+        }
+    }
+
+    impl Drop for FakeProc {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.0).ok();
+        }
+    }
+
+    const STAT_BEFORE: &str = "cpu  100 0 100 700 100 0 0 0 0 0
+cpu0 50 0 50 350 50 0 0 0 0 0
+cpu1 50 0 50 350 50 0 0 0 0 0
+intr 1 2 3
+ctxt 1000
+";
+
+    const STAT_AFTER: &str = "cpu  400 0 100 1300 200 0 0 0 0 0
+cpu0 350 0 50 450 150 0 0 0 0 0
+cpu1 50 0 50 850 50 0 0 0 0 0
+intr 4 5 6
+ctxt 2000
+";
+
+    const NET_DEV_BEFORE: &str = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:    5000      10    0    0    0     0          0         0     5000      10    0    0    0     0       0          0
+  eth0:    1000      10    0    0    0     0          0         0     2000      10    0    0    0     0       0          0
+ wlan0:     300       1    0    0    0     0          0         0      700       1    0    0    0     0       0          0
+";
+
+    const NET_DEV_AFTER: &str = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:   90000      10    0    0    0     0          0         0    90000      10    0    0    0     0       0          0
+  eth0:    3048      10    0    0    0     0          0         0     2000      10    0    0    0     0       0          0
+ wlan0:     300       1    0    0    0     0          0         0      700       1    0    0    0     0       0          0
+";
+
+    const DISKSTATS_BEFORE: &str = "   7       0 loop0 10 0 1000 0 0 0 0 0 0 0 0
+   8       0 sda 100 0 2000 0 50 0 1000 0 0 0 0
+   8       1 sda1 100 0 1500 0 50 0 800 0 0 0 0
+ 259       0 nvme0n1 10 0 100 0 10 0 300 0 0 0 0
+ 259       1 nvme0n1p1 10 0 100 0 10 0 300 0 0 0 0
+ 253       0 dm-0 10 0 5000 0 0 0 0 0 0 0 0
+";
+
+    const DISKSTATS_AFTER: &str = "   7       0 loop0 10 0 9000 0 0 0 0 0 0 0 0
+   8       0 sda 100 0 2004 0 50 0 1000 0 0 0 0
+   8       1 sda1 100 0 1504 0 50 0 800 0 0 0 0
+ 259       0 nvme0n1 10 0 100 0 10 0 300 0 0 0 0
+ 259       1 nvme0n1p1 10 0 100 0 10 0 300 0 0 0 0
+ 253       0 dm-0 10 0 9000 0 0 0 0 0 0 0 0
+";
+
+    #[test]
+    fn test_proc_cpu_global() {
+        let fake = FakeProc::new("cpu_global");
+        fake.write("stat", STAT_BEFORE);
+        let mut cpu = ProcCpu::new(&fake.0, false);
+        assert_eq!(cpu.read(), Some(vec![(200, 1000)]));
+        assert_eq!(cpu.sample(), None);
+
+        fake.write("stat", STAT_AFTER);
+        // 300 of 1000 jiffies were busy
+        assert_eq!(cpu.sample(), Some(30.0));
+        // No jiffies have passed
+        assert_eq!(cpu.sample(), Some(0.0));
+    }
+
+    #[test]
+    fn test_proc_cpu_per_core() {
+        let fake = FakeProc::new("cpu_per_core");
+        fake.write("stat", STAT_BEFORE);
+        let mut cpu = ProcCpu::new(&fake.0, true);
+        assert_eq!(cpu.read(), Some(vec![(100, 500), (100, 500)]));
+        assert_eq!(cpu.sample(), None);
+
+        fake.write("stat", STAT_AFTER);
+        // cpu0 was busy for 300 of 500 jiffies, cpu1 for none of them
+        assert_eq!(cpu.sample(), Some(60.0));
+
+        // A core taken offline has nothing to compare against
+        fake.write(
+            "stat",
+            "cpu  400 0 100 1300 200 0 0 0 0 0\ncpu0 350 0 50 450 150 0 0 0 0 0\n",
+        );
+        assert_eq!(cpu.sample(), None);
+    }
+
+    #[test]
+    fn test_proc_cpu_missing() {
+        let fake = FakeProc::new("cpu_missing");
+        let mut cpu = ProcCpu::new(&fake.0, false);
+        assert_eq!(cpu.sample(), None);
+    }
+
+    #[test]
+    fn test_proc_network() {
+        let fake = FakeProc::new("network");
+        fake.write("net/dev", NET_DEV_BEFORE);
+        let mut network = ProcNetwork::new(&fake.0);
+        // Loopback isn't counted
+        assert_eq!(network.read(), Some(4000));
+        assert_eq!(network.sample(), None);
+
+        fake.write("net/dev", NET_DEV_AFTER);
+        assert_eq!(network.read(), Some(6048));
+        assert!(network.sample().unwrap() > 0.0);
+
+        // A counter that goes backwards is no activity
+        fake.write("net/dev", NET_DEV_BEFORE);
+        assert_eq!(network.sample(), Some(0.0));
+    }
+
+    #[test]
+    fn test_proc_disk() {
+        let fake = FakeProc::new("disk");
+        fake.write("diskstats", DISKSTATS_BEFORE);
+        let mut disk = ProcDisk::new(&fake.0);
+        // Only sda & nvme0n1, the partitions & virtual devices are excluded
+        assert_eq!(disk.read(), Some(3400 * BYTES_PER_SECTOR));
+        assert_eq!(disk.sample(), None);
+
+        fake.write("diskstats", DISKSTATS_AFTER);
+        assert_eq!(disk.read(), Some(3404 * BYTES_PER_SECTOR));
+        assert!(disk.sample().unwrap() > 0.0);
+
+        // No sectors read or written since the previous sample
+        assert_eq!(disk.sample(), Some(0.0));
+    }
+
+    #[test]
+    fn test_signal_state_from_averages() {
+        assert_eq!(
+            SignalState::from_averages(Some(50.0), 50.0, Some(10.0), 10.0),
+            SignalState {
+                active: true,
+                idle: true
+            }
+        );
+        assert_eq!(
+            SignalState::from_averages(Some(49.9), 50.0, Some(10.1), 10.0),
+            SignalState::default()
+        );
+        assert_eq!(
+            SignalState::from_averages(None, 0.0, None, 100.0),
+            SignalState::default()
+        );
+    }
+
+    #[test]
+    fn test_signal_state_any_all() {
+        let active = SignalState {
+            active: true,
+            idle: false,
+        };
+        let idle = SignalState {
+            active: false,
+            idle: true,
+        };
+        let neither = SignalState::default();
+
+        assert_eq!(SignalState::any(&[active, idle]), active);
+        assert_eq!(SignalState::any(&[idle, idle]), idle);
+        assert_eq!(SignalState::any(&[idle, neither]), neither);
+        assert_eq!(SignalState::any(&[]), neither);
+
+        assert_eq!(SignalState::all(&[active, idle]), idle);
+        assert_eq!(SignalState::all(&[active, active]), active);
+        assert_eq!(SignalState::all(&[active, neither]), neither);
+        assert_eq!(SignalState::all(&[]), neither);
+    }
+
+    const AGGREGATORS: [Aggregator; 4] = [
+        Aggregator::Mean,
+        Aggregator::Median,
+        Aggregator::Percentile(90),
+        Aggregator::Ewma(0.5),
+    ];
+
+    const PAUSE_THRESHOLD: f64 = 10.0;
+    const RESUME_THRESHOLD: f64 = 40.0;
+
+    /// Replay a cpu trace, one sample a second, from a running timer, in the same way as the auto-pause & auto-resume of the application state
+    /// Each second is decided from the aggregate of the previous `window` samples, every decision is acted upon
+    fn replay(
+        trace: &[f32],
+        aggregator: Aggregator,
+        window: usize,
+        band: f64,
+        dwell_sec: i64,
+    ) -> Vec<(i64, AutoTransition)> {
+        let mut guard = TransitionGuard::default();
+        let mut paused = false;
+        let mut transitions = vec![];
+        for (index, now_sec) in (0..trace.len()).zip(0..) {
+            let avg = index
+                .checked_sub(window - 1)
+                .and_then(|start| aggregator.aggregate(&trace[start..=index]));
+            let (pause, resume) = guard.get_thresholds(PAUSE_THRESHOLD, RESUME_THRESHOLD, band);
+            let state = SignalState::from_averages(avg, resume, avg, pause);
+            if let Some(transition) = guard.decide(
+                now_sec,
+                dwell_sec,
+                paused.then_some(PauseReason::Cpu),
+                AutoEnabled {
+                    pause: true,
+                    resume: true,
+                },
+                state,
+            ) {
+                guard.record(transition, now_sec);
+                paused = transition == AutoTransition::Pause;
+                transitions.push((now_sec, transition));
+            }
+        }
+        transitions
+    }
+
+    #[test]
+    fn test_aggregator() {
+        let samples = [1.0, 2.0, 3.0, 10.0];
+        assert_eq!(Aggregator::Mean.aggregate(&samples), Some(4.0));
+        assert_eq!(Aggregator::Median.aggregate(&samples), Some(2.5));
+        assert_eq!(Aggregator::Median.aggregate(&samples[..3]), Some(2.0));
+        assert_eq!(Aggregator::Percentile(50).aggregate(&samples), Some(2.0));
+        assert_eq!(Aggregator::Percentile(100).aggregate(&samples), Some(10.0));
+        // Clamped to the 1st percentile
+        assert_eq!(Aggregator::Percentile(0).aggregate(&samples), Some(1.0));
+        assert_eq!(Aggregator::Ewma(0.5).aggregate(&samples), Some(6.125));
+        for aggregator in AGGREGATORS {
+            assert_eq!(aggregator.aggregate(&[]), None);
+        }
+    }
+
+    #[test]
+    fn test_decide() {
+        let guard = TransitionGuard::default();
+        let active = SignalState {
+            active: true,
+            idle: false,
+        };
+        let idle = SignalState {
+            active: false,
+            idle: true,
+        };
+        let enabled = AutoEnabled {
+            pause: true,
+            resume: true,
+        };
+        let cpu = Some(PauseReason::Cpu);
+        assert_eq!(
+            guard.decide(0, 0, None, enabled, idle),
+            Some(AutoTransition::Pause)
+        );
+        assert_eq!(
+            guard.decide(0, 0, cpu, enabled, active),
+            Some(AutoTransition::Resume)
+        );
+        assert_eq!(guard.decide(0, 0, None, enabled, active), None);
+        assert_eq!(guard.decide(0, 0, cpu, enabled, idle), None);
+
+        // A pause for any other reason isn't resumed by activity
+        for reason in [
+            PauseReason::Manual,
+            PauseReason::Schedule,
+            PauseReason::Meeting,
+            PauseReason::AfterBreak,
+            PauseReason::EndOfDay,
+            PauseReason::Process,
+        ] {
+            assert_eq!(guard.decide(0, 0, Some(reason), enabled, active), None);
+        }
+
+        // A disabled transition is never decided
+        let disabled = AutoEnabled::default();
+        assert_eq!(guard.decide(0, 0, None, disabled, idle), None);
+        assert_eq!(guard.decide(0, 0, cpu, disabled, active), None);
+    }
+
+    #[test]
+    fn test_replay_spike() {
+        let trace = [2.0, 2.0, 2.0, 2.0, 100.0, 2.0, 2.0, 2.0, 2.0];
+        let spike = vec![
+            (2, AutoTransition::Pause),
+            (4, AutoTransition::Resume),
+            (7, AutoTransition::Pause),
+        ];
+        // The mean of the spike's window is below the resume threshold, and the median ignores it
+        assert_eq!(
+            replay(&trace, Aggregator::Mean, 3, 5.0, 0),
+            vec![(2, AutoTransition::Pause)]
+        );
+        assert_eq!(
+            replay(&trace, Aggregator::Median, 3, 5.0, 0),
+            vec![(2, AutoTransition::Pause)]
+        );
+        // The spike resumes the 90th percentile, and the ewma, and keeps them above the pause threshold until it leaves the window
+        assert_eq!(replay(&trace, Aggregator::Percentile(90), 3, 5.0, 0), spike);
+        assert_eq!(replay(&trace, Aggregator::Ewma(0.5), 3, 5.0, 0), spike);
+    }
+
+    #[test]
+    fn test_replay_hysteresis_band() {
+        // A window of a single sample, so every aggregator sees the raw trace
+        let trace = [5.0, 42.0, 44.0, 42.0, 50.0, 12.0, 8.0, 12.0, 4.0, 44.0];
+        for aggregator in AGGREGATORS {
+            // Flapping between the thresholds, and the thresholds widened by the band, is suppressed
+            assert_eq!(
+                replay(&trace, aggregator, 1, 5.0, 0),
+                vec![
+                    (0, AutoTransition::Pause),
+                    (4, AutoTransition::Resume),
+                    (8, AutoTransition::Pause),
+                ]
+            );
+            assert_eq!(
+                replay(&trace, aggregator, 1, 0.0, 0),
+                vec![
+                    (0, AutoTransition::Pause),
+                    (1, AutoTransition::Resume),
+                    (6, AutoTransition::Pause),
+                    (9, AutoTransition::Resume),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_dwell() {
+        let trace = [5.0, 50.0, 50.0, 50.0, 5.0, 5.0, 5.0, 50.0];
+        for aggregator in AGGREGATORS {
+            // Nothing is decided until the dwell time since the previous transition has expired
+            assert_eq!(
+                replay(&trace, aggregator, 1, 0.0, 3),
+                vec![
+                    (0, AutoTransition::Pause),
+                    (3, AutoTransition::Resume),
+                    (6, AutoTransition::Pause),
+                ]
+            );
+            assert_eq!(
+                replay(&trace, aggregator, 1, 0.0, 0),
+                vec![
+                    (0, AutoTransition::Pause),
+                    (1, AutoTransition::Resume),
+                    (4, AutoTransition::Pause),
+                    (7, AutoTransition::Resume),
+                ]
+            );
+        }
+    }
+}
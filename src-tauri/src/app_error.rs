@@ -1,10 +1,11 @@
 use thiserror::Error;
-use tracing::error;
 
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("internal error:")]
     Internal(String),
+    #[error("migration {0} failed: {1}")]
+    Migration(usize, sqlx::Error),
     #[error("not found")]
     SqlxError(#[from] sqlx::Error),
 }
@@ -1,5 +1,6 @@
 use crate::{
     application_state::{ApplicationState, system_tray::MenuEntry},
+    db::PauseReason,
     message_handler::{MsgFE, MsgI},
 };
 
@@ -25,12 +26,34 @@ impl MenuManipulation {
         state.send(MsgI::ToFrontEnd(MsgFE::NextBreak));
     }
 
+    /// Update the systemtray upcoming segment text
+    pub fn update_upcoming(state: &ApplicationState) {
+        let title = state.get_upcoming_title();
+        state
+            .get_menu_entry(MenuEntry::Upcoming)
+            .and_then(|i| i.as_menuitem().and_then(|i| i.set_text(title).ok()));
+    }
+
     /// Update the systemtray `Puased/Resume` item
     pub fn update_pause(state: &ApplicationState, paused: bool) {
-        let title = if paused {
-            "Resume"
-        } else {
-            MenuEntry::Pause.as_str()
+        let resume_at = state
+            .get_resume_at()
+            .and_then(|i| chrono::DateTime::from_timestamp(i, 0))
+            .map(|i| i.with_timezone(&chrono::Local).format("%a %H:%M"));
+        let title = match (state.get_pause_reason().filter(|_| paused), resume_at) {
+            (Some(_), Some(resume_at)) => format!("Resume, paused until {resume_at}"),
+            (Some(PauseReason::Manual | PauseReason::AfterBreak | PauseReason::Suspend), None) => {
+                String::from("Resume")
+            }
+            (Some(PauseReason::Cpu), None) => String::from("Resume, auto-paused"),
+            (Some(PauseReason::Schedule), None) => String::from("Resume, outside working hours"),
+            (Some(PauseReason::Meeting), None) => String::from("Resume, in a meeting"),
+            (Some(PauseReason::EndOfDay), None) => String::from("Resume, end of day"),
+            (Some(PauseReason::Process), None) => state.get_watched_process().map_or_else(
+                || String::from("Resume, process running"),
+                |process| format!("Resume, {process} running"),
+            ),
+            (None, _) => MenuEntry::Pause.get_text(),
         };
 
         state
@@ -39,6 +62,10 @@ impl MenuManipulation {
         state
             .get_menu_entry(MenuEntry::Session)
             .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(!paused).ok()));
+        // Resuming from a meeting is done with the `Pause` entry
+        state
+            .get_menu_entry(MenuEntry::Meeting)
+            .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(!paused).ok()));
 
         state
             .get_menu_entry(MenuEntry::Pause)
@@ -49,5 +76,6 @@ impl MenuManipulation {
     pub fn update_all(state: &ApplicationState) {
         Self::update_next_break(state);
         Self::update_session_number(state);
+        Self::update_upcoming(state);
     }
 }
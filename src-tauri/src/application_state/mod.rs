@@ -7,23 +7,41 @@ use std::{
 };
 
 use auto_launch::AutoLaunch;
+use chrono::{Datelike, Timelike};
 use rand::seq::IndexedRandom;
+use serde::Serialize;
 use sqlx::SqlitePool;
 use tauri::{AppHandle, Emitter, Wry, menu::MenuItemKind};
 use tokio::{sync::broadcast::Sender, task::JoinHandle};
 
 use crate::{
     MAIN_WINDOW,
+    activity::{
+        ActivitySample, Aggregator, AutoEnabled, AutoTransition, SignalState, TransitionGuard,
+    },
     app_error::AppError,
     application_state::{
         menu::MenuManipulation,
-        system_tray::{MenuEntry, change_menu_entry_status, set_icon},
+        system_tray::{
+            MenuEntry, TRAY_DELAY_MINUTES, TRAY_POSTPONE_MINUTES, change_menu_awaiting_ready,
+            change_menu_break_warning, change_menu_entry_status, set_icon, set_profiles, set_title,
+        },
         window_action::WindowAction,
     },
     check_version,
-    db::ModelSettings,
-    message_handler::{MsgB, MsgFE, MsgI, MsgWV},
-    request_handlers::{CpuMeasure, FrontEndState, ShowTimer},
+    db::{
+        CpuMode, EventType, ModelActiveDay, ModelActivity, ModelActivityHistory, ModelAlignment,
+        ModelCycleSegment, ModelEvent, ModelFlow, ModelInterruption, ModelPause, ModelProcessWatch,
+        ModelProfile, ModelReminder, ModelResumeBehaviour, ModelSchedule, ModelSettings,
+        ModelStats, ModelTimerState, PauseReason, ReminderDisplay, ResumeBehaviour, SessionOutcome,
+        SessionType, SignalSource, SuspendPolicy, now_as_sec,
+    },
+    heartbeat::heartbeat_process,
+    message_handler::{
+        MsgAC, MsgAL, MsgB, MsgFE, MsgFL, MsgI, MsgIR, MsgP, MsgPW, MsgRB, MsgRM, MsgS, MsgWH,
+        MsgWV, PauseUntil, Suspension,
+    },
+    request_handlers::{CpuMeasure, FrontEndState, ShowTimer, ShowWarning},
 };
 
 mod menu;
@@ -32,11 +50,23 @@ mod window_action;
 
 pub use system_tray::create_system_tray;
 
-/// Store a most 15 minutes worth of cpu data in the vecdeque
+/// Store a most 15 minutes worth of cpu, network, and disk, data in each vecdeque
 const CPU_VECDEQUE_LEN: usize = 60 * 15;
 
 const ONE_WEEK_AS_SEC: u64 = 60 * 60 * 24 * 7;
 
+/// How often, whilst the timer is running, to save a snapshot of the timer state
+const SNAPSHOT_INTERVAL_SEC: u64 = 15;
+
+/// Number of seconds of cpu usage, above the auto-resume threshold, that counts as the user being ready after a break
+const READY_ACTIVITY_SEC: u16 = 10;
+
+/// Longest pause, 4 hours, that can be credited as a natural break, anything longer is treated as time away from work, rather than a break
+const NATURAL_BREAK_MAX_SEC: u64 = 60 * 60 * 4;
+
+/// Minimum number of seconds that a reminder is shown for, either next to the systemtray icon, or as an overlay
+const REMINDER_MIN_SHOW_SEC: u16 = 5;
+
 /// Load the Oblique Stratergies into a Lazylock vec
 pub static STRATEGIES: LazyLock<Vec<String>> = LazyLock::new(|| {
     include_str!("../../oblique.txt")
@@ -64,12 +94,20 @@ impl std::fmt::Display for BreakVariant {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum SessionStatus {
     Work,
+    /// An open-ended work session, whilst flow mode is enabled, which counts up until ended
+    Flow,
     Break(BreakVariant),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum Timer {
-    Paused((Instant, Instant)),
+    Paused {
+        original: Instant,
+        paused: Instant,
+        reason: PauseReason,
+        /// Unix timestamp, in seconds, when the timer will automatically resume
+        resume_at: Option<i64>,
+    },
     Work(Instant),
 }
 impl Default for Timer {
@@ -79,16 +117,46 @@ impl Default for Timer {
 }
 
 impl Timer {
-    fn toggle(self) -> Self {
+    /// The reason is only used when pausing
+    fn toggle(self, reason: PauseReason) -> Self {
         match self {
-            Self::Paused((original, paused)) => Self::Work(original + paused.elapsed()),
-            Self::Work(original) => Self::Paused((original, std::time::Instant::now())),
+            Self::Paused {
+                original, paused, ..
+            } => Self::Work(original + paused.elapsed()),
+            Self::Work(original) => Self::Paused {
+                original,
+                paused: std::time::Instant::now(),
+                reason,
+                resume_at: None,
+            },
+        }
+    }
+    /// Pause, or keep paused, until the given unix timestamp
+    fn pause_until(self, resume_at: i64) -> Self {
+        let (original, paused) = match self {
+            Self::Paused {
+                original, paused, ..
+            } => (original, paused),
+            Self::Work(original) => (original, std::time::Instant::now()),
+        };
+        Self::Paused {
+            original,
+            paused,
+            reason: PauseReason::Manual,
+            resume_at: Some(resume_at),
         }
     }
     fn reset(self) -> Self {
         match self {
             Self::Work(_) => Self::default(),
-            Self::Paused(_) => Self::Paused((std::time::Instant::now(), std::time::Instant::now())),
+            Self::Paused {
+                reason, resume_at, ..
+            } => Self::Paused {
+                original: std::time::Instant::now(),
+                paused: std::time::Instant::now(),
+                reason,
+                resume_at,
+            },
         }
     }
 }
@@ -102,18 +170,175 @@ impl From<&ApplicationState> for FrontEndState {
             auto_resume_threshold: state.settings.auto_resume_threshold,
             auto_resume_timespan_sec: state.settings.auto_resume_timespan_sec,
             auto_resume: state.settings.auto_resume,
+            break_warning_sec: state.settings.break_warning_sec,
+            carry_over_skipped: state.settings.carry_over_skipped,
+            daily_cap_sec: state.settings.daily_cap_sec,
             fullscreen: state.settings.fullscreen,
             long_break_as_sec: state.settings.long_break_as_sec,
             number_session_before_break: state.settings.number_session_before_break,
+            overwork_limit_sec: state.settings.overwork_limit_sec,
             paused: state.get_paused(),
+            pause_reason: state.get_pause_reason(),
+            postpone_limit_per_day: state.settings.postpone_limit_per_day,
+            ready_after_break: state.settings.ready_after_break,
+            restore_max_age_sec: state.settings.restore_max_age_sec,
             session_as_sec: state.settings.session_as_sec,
             short_break_as_sec: state.settings.short_break_as_sec,
             start_on_boot: ApplicationState::get_auto_launch()
                 .is_some_and(|i| i.is_enabled().unwrap_or_default()),
+            suspend_policy: state.settings.suspend_policy,
+        }
+    }
+}
+
+/// Flags of the current phase of the day, and of the work session, which change how the timer moves between work sessions & breaks
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+struct SessionFlags {
+    /// Whether the break has ended, and the next work session is waiting for the user to be ready
+    awaiting_ready: bool,
+    /// Whether the daily cap of active use has been reached, reset at the start of the next local day
+    end_of_day: bool,
+    /// Whether the work session is in its pre-break phase, during which the break warning is shown
+    pre_break: bool,
+}
+
+/// Track the session currently in progress, so that it can be inserted into the stats table once finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SessionRecord {
+    planned_sec: u16,
+    paused: bool,
+    /// Discarded when resuming from a pause
+    interrupted: bool,
+    started_at: i64,
+}
+
+impl SessionRecord {
+    fn new(planned_sec: u16, paused: bool) -> Self {
+        Self {
+            planned_sec,
+            paused,
+            interrupted: false,
+            started_at: now_as_sec(),
+        }
+    }
+
+    /// Convert into a stats row, `actual_sec` is the amount of time the session lasted, which can be more than planned
+    fn finish(self, session_status: SessionStatus, actual_sec: u16) -> ModelStats {
+        let outcome = if self.interrupted {
+            SessionOutcome::Interrupted
+        } else if actual_sec < self.planned_sec {
+            SessionOutcome::CutShort
+        } else if self.paused {
+            SessionOutcome::Paused
+        } else {
+            SessionOutcome::Completed
+        };
+        ModelStats {
+            session_type: SessionType::from(session_status),
+            started_at: self.started_at,
+            ended_at: now_as_sec(),
+            planned_sec: self.planned_sec,
+            actual_sec: u32::from(actual_sec),
+            outcome,
         }
     }
 }
 
+/// Active computer use, tracked from the cpu usage, regardless of whether the timer is paused
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct ActiveUse {
+    /// Seconds of active use since the last break, or since being idle for at least a short break
+    continuous_sec: u32,
+    /// Seconds since the cpu usage was last above the auto-resume threshold
+    idle_sec: u16,
+    /// The local day that `today_sec` is for, YYYY-MM-DD
+    day: String,
+    today_sec: u32,
+}
+
+/// Sums, and counts, of every activity signal since the start of the current minute, stored as per-minute averages
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ActivityMinute {
+    /// Unix time at the start of the minute
+    minute: i64,
+    cpu_mode: CpuMode,
+    cpu: (f32, u16),
+    network_kb: (f32, u16),
+    disk_kb: (f32, u16),
+}
+
+impl ActivityMinute {
+    /// Add a sample, if the sample is from a later minute, the finished minute is returned, and a new minute started
+    /// A change in cpu mode discards the cpu samples measured by the previous mode
+    fn push(&mut self, now_sec: i64, sample: ActivitySample) -> Option<ModelActivityHistory> {
+        let minute = now_sec - now_sec.rem_euclid(60);
+        let finished = (minute != self.minute).then(|| self.get_average());
+        if finished.is_some() {
+            *self = Self {
+                minute,
+                cpu_mode: sample.cpu_mode,
+                ..Self::default()
+            };
+        } else if sample.cpu_mode != self.cpu_mode {
+            self.cpu_mode = sample.cpu_mode;
+            self.cpu = (0.0, 0);
+        }
+        for (sum, value) in [
+            (&mut self.cpu, sample.cpu),
+            (&mut self.network_kb, sample.network_kb),
+            (&mut self.disk_kb, sample.disk_kb),
+        ] {
+            if let Some(value) = value {
+                *sum = (sum.0 + value, sum.1.saturating_add(1));
+            }
+        }
+        finished.flatten()
+    }
+
+    /// The average of each signal, `None` if nothing has been sampled yet
+    fn get_average(&self) -> Option<ModelActivityHistory> {
+        let average = |(sum, count): (f32, u16)| (count > 0).then(|| sum / f32::from(count));
+        let history = ModelActivityHistory {
+            minute: self.minute,
+            cpu: average(self.cpu),
+            cpu_mode: self.cpu_mode,
+            network_kb: average(self.network_kb),
+            disk_kb: average(self.disk_kb),
+        };
+        (history.cpu.is_some() || history.network_kb.is_some() || history.disk_kb.is_some())
+            .then_some(history)
+    }
+}
+
+/// A recurring reminder, and the number of seconds it has counted down since it was last shown
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReminderTimer {
+    reminder: ModelReminder,
+    elapsed_sec: u32,
+}
+
+/// Calculate the average of the previous `limit` seconds of usage
+fn calc_aggregate(usage: &VecDeque<f32>, limit: u16, aggregator: Aggregator) -> Option<f32> {
+    if usage.len() < limit.into() {
+        return None;
+    }
+    let samples = usage
+        .iter()
+        .take(limit.into())
+        .rev()
+        .copied()
+        .collect::<Vec<_>>();
+    aggregator.aggregate(&samples)
+}
+
+/// Add the latest usage to the front of the vecdeque, removing the oldest if full
+fn push_usage(usage: &mut VecDeque<f32>, value: f32) {
+    if usage.len() >= CPU_VECDEQUE_LEN {
+        usage.pop_back();
+    }
+    usage.push_front(value);
+}
+
 /// fuzzy second to minutes conversion
 fn format_sec_to_min(sec: u16) -> String {
     if sec <= 60 {
@@ -125,19 +350,51 @@ fn format_sec_to_min(sec: u16) -> String {
 }
 
 pub struct ApplicationState {
+    active_use: ActiveUse,
+    activity: ModelActivity,
+    activity_minute: ActivityMinute,
+    alignment: ModelAlignment,
     app_handle: AppHandle,
+    carried_break_sec: u16,
     cpu_usage: VecDeque<f32>,
+    cycle: Vec<ModelCycleSegment>,
+    cycle_index: u8,
     data_location: PathBuf,
+    disk_usage: VecDeque<f32>,
+    flags: SessionFlags,
+    flow: ModelFlow,
     heartbeat_process: Option<Arc<JoinHandle<()>>>,
+    network_usage: VecDeque<f32>,
     pause_after_break: bool,
+    postponed_break: Option<BreakVariant>,
+    postpones_remaining: u8,
+    /// Regexes of every enabled process watch
+    process_patterns: Vec<regex::Regex>,
+    /// Reminders that were due during the break warning, shown one at a time once it has ended
+    queued_reminders: VecDeque<ModelReminder>,
+    /// When to hide the main window after it was shown for a reminder overlay
+    reminder_overlay_until: Option<Instant>,
+    /// When to remove a reminder notification from next to the systemtray icon
+    reminder_title_until: Option<Instant>,
+    reminders: Vec<ReminderTimer>,
+    resume_behaviours: Vec<ModelResumeBehaviour>,
+    schedule: ModelSchedule,
     session_count: u8,
+    session_record: SessionRecord,
     session_status: SessionStatus,
     settings: ModelSettings,
+    snapshot_time: std::time::Instant,
     sqlite: SqlitePool,
     start_time: std::time::Instant,
     sx: Sender<MsgI>,
     system_tray_menu: tauri::menu::Menu<tauri::Wry>,
     timer: Timer,
+    /// The previous auto-pause or auto-resume, to apply the hysteresis band & dwell time
+    transition_guard: TransitionGuard,
+    /// The first running process that matched a process watch, at the last check
+    watched_process: Option<String>,
+    /// Whether the last check of the schedule was inside working hours, `None` if not yet checked
+    working_hours: Option<bool>,
 }
 
 impl ApplicationState {
@@ -150,101 +407,196 @@ impl ApplicationState {
         system_tray_menu: tauri::menu::Menu<tauri::Wry>,
     ) -> Self {
         Self {
+            active_use: ActiveUse::default(),
+            activity: ModelActivity::default(),
+            activity_minute: ActivityMinute::default(),
+            alignment: ModelAlignment::default(),
             app_handle,
+            carried_break_sec: 0,
             cpu_usage: VecDeque::with_capacity(CPU_VECDEQUE_LEN),
+            cycle: vec![],
+            cycle_index: 0,
             data_location,
+            disk_usage: VecDeque::with_capacity(CPU_VECDEQUE_LEN),
+            flags: SessionFlags::default(),
+            flow: ModelFlow::default(),
             heartbeat_process: None,
+            network_usage: VecDeque::with_capacity(CPU_VECDEQUE_LEN),
             pause_after_break: false,
+            postponed_break: None,
+            postpones_remaining: 0,
+            process_patterns: vec![],
+            queued_reminders: VecDeque::new(),
+            reminder_overlay_until: None,
+            reminder_title_until: None,
+            reminders: vec![],
+            resume_behaviours: vec![],
+            schedule: ModelSchedule::default(),
             session_count: 0,
+            session_record: SessionRecord::new(settings.session_as_sec, false),
             session_status: SessionStatus::Work,
             settings,
+            snapshot_time: std::time::Instant::now(),
             sqlite,
             start_time: std::time::Instant::now(),
             sx,
             system_tray_menu,
             timer: Timer::default(),
+            transition_guard: TransitionGuard::default(),
+            watched_process: None,
+            working_hours: None,
         }
     }
 
-    /// Calculate the average cpu usage over the previous `limit` seconds
-    pub fn calc_cpu_average(&self, limit: u16) -> Option<f32> {
-        if self.cpu_usage.len() < limit.into() {
-            return None;
+    /// Calculate the aggregate cpu usage over the previous `limit` seconds
+    pub fn calc_cpu_aggregate(&self, limit: u16) -> Option<f32> {
+        calc_aggregate(&self.cpu_usage, limit, self.activity.get_aggregator())
+    }
+
+    /// Whether the activity source counts as active, and whether it counts as idle
+    /// The cpu signal uses the auto-pause & auto-resume thresholds, widened by the hysteresis band, network & disk signals with a threshold of 0 are excluded
+    fn get_activity_state(&self, cpu_measure: &CpuMeasure) -> SignalState {
+        let aggregator = self.activity.get_aggregator();
+        let (pause_threshold, resume_threshold) = self.transition_guard.get_thresholds(
+            f64::from(self.settings.auto_pause_threshold),
+            f64::from(self.settings.auto_resume_threshold),
+            f64::from(self.activity.hysteresis_band),
+        );
+        let cpu = SignalState::from_averages(
+            cpu_measure.resume,
+            resume_threshold,
+            cpu_measure.pause,
+            pause_threshold,
+        );
+        let network = (self.activity.network_threshold_kb > 0).then(|| {
+            let avg = calc_aggregate(
+                &self.network_usage,
+                self.activity.network_timespan_sec,
+                aggregator,
+            );
+            let threshold = f64::from(self.activity.network_threshold_kb);
+            SignalState::from_averages(avg, threshold, avg, threshold)
+        });
+        let disk = (self.activity.disk_threshold_kb > 0).then(|| {
+            let avg = calc_aggregate(
+                &self.disk_usage,
+                self.activity.disk_timespan_sec,
+                aggregator,
+            );
+            let threshold = f64::from(self.activity.disk_threshold_kb);
+            SignalState::from_averages(avg, threshold, avg, threshold)
+        });
+        let signals = [Some(cpu), network, disk]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        match self.activity.source {
+            SignalSource::Cpu => cpu,
+            SignalSource::Network => network.unwrap_or_default(),
+            SignalSource::Disk => disk.unwrap_or_default(),
+            SignalSource::Any => SignalState::any(&signals),
+            SignalSource::All => SignalState::all(&signals),
         }
-        Some(self.cpu_usage.iter().take(limit.into()).sum::<f32>() / f32::from(limit))
     }
 
     /// Handle all internal messages about emitting messages to the frontend, and send to the frontend
     pub fn emit_to_frontend(&self, msg_to_frontend: MsgFE) {
         let event_name = msg_to_frontend.as_str();
         match msg_to_frontend {
-            MsgFE::GoToSettings => {
-                let on_break = self.get_on_break();
-                if !on_break {
-                    self.app_handle
-                        .emit_str(MAIN_WINDOW, event_name.to_owned())
-                        .ok();
-                    WindowAction::toggle_visibility(&self.app_handle, false);
-                }
-            }
-            MsgFE::Cpu(value) => {
-                self.app_handle.emit_to(MAIN_WINDOW, event_name, value).ok();
-            }
-
-            MsgFE::NextBreak => {
+            MsgFE::GoToSettings => self.emit_go_to_settings(event_name),
+            MsgFE::GoToTimer => self.emit_go_to_timer(event_name),
+            MsgFE::AwaitingReady | MsgFE::SessionEnded => {
                 self.app_handle
-                    .emit_to(MAIN_WINDOW, event_name, self.get_next_break_title())
+                    .emit_str(MAIN_WINDOW, event_name.to_owned())
                     .ok();
             }
-
+            MsgFE::Error => self.emit(event_name, "Internal Error"),
+            MsgFE::GetSettings => self.emit(event_name, self.get_frontend_state()),
+            MsgFE::NextBreak => self.emit(event_name, self.get_next_break_title()),
             MsgFE::OnBreak => {
-                self.app_handle
-                    .emit_to(MAIN_WINDOW, event_name, self.get_current_timer_left())
-                    .ok();
-            }
-
-            MsgFE::Error => {
-                self.app_handle
-                    .emit_to(MAIN_WINDOW, event_name, "Internal Error")
-                    .ok();
-            }
-
-            MsgFE::GetSettings => {
-                self.app_handle
-                    .emit_to(MAIN_WINDOW, event_name, self.get_frontend_state())
-                    .ok();
+                let left = if self.get_on_break() {
+                    self.get_current_timer_left()
+                } else {
+                    0
+                };
+                self.emit(event_name, left);
             }
             MsgFE::SessionsBeforeLong => {
-                self.app_handle
-                    .emit_to(
-                        MAIN_WINDOW,
-                        event_name,
-                        self.get_sessions_before_long_title(),
-                    )
-                    .ok();
+                self.emit(event_name, self.get_sessions_before_long_title());
             }
-            MsgFE::GoToTimer => {
-                let (break_time, strategy) = self.get_break_settings();
-                self.app_handle
-                    .emit_to(MAIN_WINDOW, "fullscreen", self.get_fullscreen())
-                    .ok();
-                self.app_handle
-                    .emit_to(
-                        MAIN_WINDOW,
-                        event_name,
-                        ShowTimer::new(break_time, strategy),
-                    )
-                    .ok();
-            }
-            MsgFE::PackageInfo(info) => {
-                self.app_handle.emit_to(MAIN_WINDOW, event_name, info).ok();
-            }
-            MsgFE::Paused(paused) => {
-                self.app_handle
-                    .emit_to(MAIN_WINDOW, event_name, paused)
-                    .ok();
+            MsgFE::BreakWarning(left) => {
+                // Clock-aligned sessions can't be extended, so there's no delay to offer
+                let delay_minutes = if self.alignment.enabled {
+                    0
+                } else {
+                    TRAY_DELAY_MINUTES
+                };
+                self.emit(event_name, ShowWarning::new(left, delay_minutes));
             }
+            MsgFE::PackageInfo(info) => self.emit(event_name, info),
+            MsgFE::Paused(paused) => self.emit(event_name, paused),
+            MsgFE::Reminder(reminder) => self.emit(event_name, reminder),
+            MsgFE::Activity(activity) => self.emit(event_name, activity),
+            MsgFE::ActivityHistory(history) => self.emit(event_name, history),
+            MsgFE::Cpu(value) => self.emit(event_name, value),
+            MsgFE::Alignment(alignment) => self.emit(event_name, alignment),
+            MsgFE::Cycle(cycle) => self.emit(event_name, cycle),
+            MsgFE::Flow(flow) => self.emit(event_name, flow),
+            MsgFE::Interruptions(interruptions) => self.emit(event_name, interruptions),
+            MsgFE::ProcessWatches(process_watches) => self.emit(event_name, process_watches),
+            MsgFE::Profiles(profiles) => self.emit(event_name, profiles),
+            MsgFE::Reminders(reminders) => self.emit(event_name, reminders),
+            MsgFE::ResumeBehaviours(behaviours) => self.emit(event_name, behaviours),
+            MsgFE::Schedule(schedule) => self.emit(event_name, schedule),
+            MsgFE::StatsHeatmap(heatmap) => self.emit(event_name, heatmap),
+            MsgFE::StatsTotals(totals) => self.emit(event_name, totals),
+        }
+    }
+
+    /// Emit a single event, with a payload, to the main window
+    fn emit<S: Serialize + Clone>(&self, event_name: &str, payload: S) {
+        self.app_handle
+            .emit_to(MAIN_WINDOW, event_name, payload)
+            .ok();
+    }
+
+    /// Show the settings view, unless on a break
+    fn emit_go_to_settings(&self, event_name: &str) {
+        if !self.get_on_break() {
+            self.app_handle
+                .emit_str(MAIN_WINDOW, event_name.to_owned())
+                .ok();
+            WindowAction::toggle_visibility(&self.app_handle, false);
+        }
+    }
+
+    /// Show the break timer, in fullscreen if set
+    fn emit_go_to_timer(&self, event_name: &str) {
+        let (break_time, strategy) = self.get_break_settings();
+        self.emit("fullscreen", self.get_fullscreen());
+        self.emit(
+            event_name,
+            ShowTimer::new(
+                break_time,
+                self.postpones_remaining,
+                TRAY_POSTPONE_MINUTES,
+                strategy,
+            ),
+        );
+    }
+
+    /// Add minutes to the current work session, and update the systemtray & frontend with the new time left
+    /// Clock-aligned sessions can't be extended
+    pub fn extend_session(&mut self, minutes: u8) {
+        if self.session_status != SessionStatus::Work || self.alignment.enabled {
+            return;
         }
+        self.session_record.planned_sec = self
+            .session_record
+            .planned_sec
+            .saturating_add(u16::from(minutes).saturating_mul(60));
+        self.save_timer_state();
+        MenuManipulation::update_all(self);
     }
 
     // Various `get_x` methods
@@ -273,19 +625,112 @@ impl ApplicationState {
 
     /// Return, in seconds, the current amount left of the onoing work - or break - session
     pub fn get_current_timer_left(&self) -> u16 {
-        let taken_since = match self.timer {
-            Timer::Paused(_) => 0,
+        self.get_aligned_left().unwrap_or_else(|| {
+            self.session_record
+                .planned_sec
+                .saturating_sub(self.get_current_timer_elapsed())
+        })
+    }
+
+    /// Return, in seconds, the amount left of a clock-aligned session, based on the local time that the session started, `None` if alignment is disabled, or in a flow session
+    /// Whilst paused, the amount left at the time of the pause is returned
+    fn get_aligned_left(&self) -> Option<u16> {
+        if !self.alignment.enabled || self.session_status == SessionStatus::Flow {
+            return None;
+        }
+        let started_at = chrono::DateTime::from_timestamp(self.session_record.started_at, 0)?
+            .with_timezone(&chrono::Local);
+        let session_sec = self
+            .alignment
+            .calc_session_sec(self.get_on_break(), started_at.num_seconds_from_midnight());
+        let now = match self.timer {
+            Timer::Paused { paused, .. } => now_as_sec()
+                .saturating_sub(i64::try_from(paused.elapsed().as_secs()).unwrap_or_default()),
+            Timer::Work(_) => now_as_sec(),
+        };
+        let left = self
+            .session_record
+            .started_at
+            .saturating_add(i64::from(session_sec))
+            .saturating_sub(now)
+            .max(0);
+        Some(u16::try_from(left).unwrap_or(u16::MAX))
+    }
+
+    /// Return, in seconds, the amount of time taken so far of the ongoing session, excluding any time paused
+    fn get_current_timer_elapsed(&self) -> u16 {
+        match self.timer {
+            Timer::Paused {
+                original: timer,
+                paused,
+                ..
+            } => u16::try_from(paused.duration_since(timer).as_secs()).unwrap_or_default(),
             Timer::Work(timer) => {
                 u16::try_from(std::time::Instant::now().duration_since(timer).as_secs())
                     .unwrap_or_default()
             }
-        };
-        match self.session_status {
-            SessionStatus::Break(break_type) => match break_type {
-                BreakVariant::Short => self.settings.short_break_as_sec.saturating_sub(taken_since),
-                BreakVariant::Long => self.settings.long_break_as_sec.saturating_sub(taken_since),
-            },
-            SessionStatus::Work => self.settings.session_as_sec.saturating_sub(taken_since),
+        }
+    }
+
+    /// Get the planned, and remaining, seconds of the ongoing session
+    /// A flow session is treated as if it was planned to end right now, and a clock-aligned session as if it was planned to last for the time taken so far plus the time left
+    fn get_session_progress(&self) -> (u16, u16) {
+        let elapsed = self.get_current_timer_elapsed();
+        match (self.session_status, self.get_aligned_left()) {
+            (SessionStatus::Flow, _) => (elapsed, 0),
+            (_, Some(left)) => (elapsed.saturating_add(left), left),
+            (_, None) => (
+                self.session_record.planned_sec,
+                self.get_current_timer_left(),
+            ),
+        }
+    }
+
+    /// Get the number of times a break can still be postponed today, clock-aligned breaks can't be postponed
+    async fn get_postpones_remaining(&self) -> u8 {
+        if self.alignment.enabled {
+            return 0;
+        }
+        match ModelEvent::count_today(&self.sqlite, EventType::BreakPostponed).await {
+            Ok(postponed) => u8::try_from(
+                i64::from(self.settings.postpone_limit_per_day)
+                    .saturating_sub(postponed)
+                    .max(0),
+            )
+            .unwrap_or_default(),
+            Err(e) => {
+                tracing::error!("{:#?}", e);
+                0
+            }
+        }
+    }
+
+    /// Get the current segment of the custom cycle, `None` if there isn't a custom cycle
+    fn get_cycle_segment(&self) -> Option<&ModelCycleSegment> {
+        self.cycle.get(usize::from(self.cycle_index))
+    }
+
+    /// Get the planned length, in seconds, of a session of the given status
+    /// The current cycle segment is used if it matches the status, else it's based on the current settings
+    /// Whilst clock-aligned, it's the full length of the work, or break, window, although the actual length depends on when the session starts
+    fn get_planned_sec(&self, session_status: SessionStatus) -> u16 {
+        if self.alignment.enabled {
+            match session_status {
+                SessionStatus::Work => return self.alignment.get_work_sec(),
+                SessionStatus::Break(_) => return self.alignment.get_break_sec(),
+                SessionStatus::Flow => (),
+            }
+        }
+        if let Some(segment) = self.get_cycle_segment()
+            && SessionStatus::from(segment.segment_type) == session_status
+        {
+            return segment.duration_sec;
+        }
+        match session_status {
+            SessionStatus::Break(BreakVariant::Short) => self.settings.short_break_as_sec,
+            SessionStatus::Break(BreakVariant::Long) => self.settings.long_break_as_sec,
+            SessionStatus::Work => self.settings.session_as_sec,
+            SessionStatus::Flow => 0,
         }
     }
 
@@ -308,8 +753,32 @@ impl ApplicationState {
         self.system_tray_menu.get(entry.get_id())
     }
 
-    /// Create a string `next break in x`, for frontend and systemtray
+    /// Create a snapshot of the current timer state
+    fn get_timer_state(&self) -> ModelTimerState {
+        let (planned_sec, remaining_sec) = self.get_session_progress();
+        ModelTimerState {
+            session_type: SessionType::from(self.session_status),
+            planned_sec,
+            remaining_sec,
+            session_count: self.session_count,
+            cycle_index: self.cycle_index,
+            paused: self.get_paused(),
+            pause_reason: self.get_pause_reason(),
+            resume_at: self.get_resume_at(),
+            pause_after_break: self.pause_after_break,
+            session_started_at: self.session_record.started_at,
+            updated_at: now_as_sec(),
+        }
+    }
+
+    /// Create a string `next break in x`, or `in flow for x` whilst in a flow session, for frontend and systemtray
     pub fn get_next_break_title(&self) -> String {
+        if self.session_status == SessionStatus::Flow {
+            return format!(
+                "in flow for {}",
+                format_sec_to_min(self.get_current_timer_elapsed())
+            );
+        }
         format!(
             "next break in {}",
             format_sec_to_min(self.get_current_timer_left())
@@ -317,13 +786,43 @@ impl ApplicationState {
     }
 
     /// Check if current on a break
-    pub fn get_on_break(&self) -> bool {
-        self.session_status != SessionStatus::Work
+    pub const fn get_on_break(&self) -> bool {
+        matches!(self.session_status, SessionStatus::Break(_))
+    }
+
+    /// Get the status of a new work session, `Flow` whilst flow mode is enabled
+    const fn get_work_status(&self) -> SessionStatus {
+        if self.flow.enabled {
+            SessionStatus::Flow
+        } else {
+            SessionStatus::Work
+        }
     }
 
     /// Check if the timer (heartbeat_process) is paused
     pub const fn get_paused(&self) -> bool {
-        matches!(self.timer, Timer::Paused(_))
+        matches!(self.timer, Timer::Paused { .. })
+    }
+
+    /// The name of the running process that paused the timer, or would have if it wasn't already paused
+    pub fn get_watched_process(&self) -> Option<&str> {
+        self.watched_process.as_deref()
+    }
+
+    /// Get the reason the timer was paused, `None` if not paused
+    pub const fn get_pause_reason(&self) -> Option<PauseReason> {
+        match self.timer {
+            Timer::Paused { reason, .. } => Some(reason),
+            Timer::Work(_) => None,
+        }
+    }
+
+    /// Get the unix timestamp when a timed pause will end, `None` if not paused, or paused indefinitely
+    pub const fn get_resume_at(&self) -> Option<i64> {
+        match self.timer {
+            Timer::Paused { resume_at, .. } => resume_at,
+            Timer::Work(_) => None,
+        }
     }
 
     /// Return a random Oblique strategy
@@ -333,16 +832,24 @@ impl ApplicationState {
             .map_or(String::new(), std::clone::Clone::clone)
     }
 
-    /// Return the number of short sessions before the next long break
-    const fn get_session_before_long_break(&self) -> u8 {
-        self.settings
-            .number_session_before_break
-            .saturating_sub(self.session_count)
+    /// Return the number of short sessions before the next long break, `None` if the custom cycle doesn't contain a long break
+    fn get_session_before_long_break(&self) -> Option<u8> {
+        if self.cycle.is_empty() {
+            Some(
+                self.settings
+                    .number_session_before_break
+                    .saturating_sub(self.session_count),
+            )
+        } else {
+            ModelCycleSegment::sessions_before_long(&self.cycle, self.cycle_index)
+        }
     }
 
     /// Create a string `next long break after x sessions`, for frontend and systemtray
     pub fn get_sessions_before_long_title(&self) -> String {
-        let number_before_long = self.get_session_before_long_break();
+        let Some(number_before_long) = self.get_session_before_long_break() else {
+            return String::from("no long break in cycle");
+        };
         let mut title = String::from("next long break after ");
         match number_before_long {
             2.. => {
@@ -353,40 +860,115 @@ impl ApplicationState {
         title
     }
 
+    /// Get the type of the next break, when there isn't a custom cycle
+    const fn get_next_break_variant(&self) -> BreakVariant {
+        if self.session_count + 1 < self.settings.number_session_before_break {
+            BreakVariant::Short
+        } else {
+            BreakVariant::Long
+        }
+    }
+
+    /// Create a string `up next: x, y minutes`, describing the segment after the current one, for the systemtray
+    pub fn get_upcoming_title(&self) -> String {
+        if self.session_status == SessionStatus::Flow {
+            return format!(
+                "up next: break, {}",
+                format_sec_to_min(self.flow.calc_break_sec(self.get_current_timer_elapsed()))
+            );
+        }
+        let upcoming = self.get_cycle_segment().and_then(|current| {
+            // Whilst a break is postponed, the current segment is still the upcoming break
+            let index = if SessionStatus::from(current.segment_type) == self.session_status {
+                ModelCycleSegment::next_index(&self.cycle, self.cycle_index)
+            } else {
+                self.cycle_index
+            };
+            self.cycle.get(usize::from(index)).map(|segment| {
+                (
+                    SessionStatus::from(segment.segment_type),
+                    segment.duration_sec,
+                    segment.label.clone(),
+                )
+            })
+        });
+        let (session_status, duration_sec, label) = upcoming.unwrap_or_else(|| {
+            let session_status = match self.session_status {
+                SessionStatus::Break(_) => SessionStatus::Work,
+                SessionStatus::Work | SessionStatus::Flow => SessionStatus::Break(
+                    self.postponed_break
+                        .unwrap_or_else(|| self.get_next_break_variant()),
+                ),
+            };
+            (session_status, self.get_planned_sec(session_status), None)
+        });
+        let name = label.unwrap_or_else(|| match session_status {
+            SessionStatus::Work | SessionStatus::Flow => String::from("work"),
+            SessionStatus::Break(break_type) => format!("{break_type} break"),
+        });
+        format!("up next: {name}, {}", format_sec_to_min(duration_sec))
+    }
+
     // Situation handlers
 
     /// Calculate the current pause & resume averages, apply pause or resume, send details to frontend
-    fn handle_auto_pause_resume(&mut self, current_usage: Option<f32>) {
-        if let Some(cpu_usage) = current_usage {
-            if self.cpu_usage.len() >= CPU_VECDEQUE_LEN {
-                self.cpu_usage.pop_back();
-            }
+    fn handle_auto_pause_resume(&mut self, activity: Option<ActivitySample>) {
+        let Some(activity) = activity else {
+            return;
+        };
+        if let Some(history) = self.activity_minute.push(now_as_sec(), activity) {
+            self.save_activity_history(history);
+        }
+        if let Some(network_kb) = activity.network_kb {
+            push_usage(&mut self.network_usage, network_kb);
+        }
+        if let Some(disk_kb) = activity.disk_kb {
+            push_usage(&mut self.disk_usage, disk_kb);
+        }
+        // A sample measured by a heartbeat that has since been replaced is ignored
+        if let Some(cpu_usage) = activity.cpu
+            && activity.cpu_mode == self.activity.cpu_mode
+        {
             let cpu_mesasure = CpuMeasure {
+                mode: activity.cpu_mode,
                 current: cpu_usage,
-                pause: self.calc_cpu_average(self.settings.auto_pause_timespan_sec),
-                resume: self.calc_cpu_average(self.settings.auto_resume_timespan_sec),
+                pause: self.calc_cpu_aggregate(self.settings.auto_pause_timespan_sec),
+                resume: self.calc_cpu_aggregate(self.settings.auto_resume_timespan_sec),
             };
 
-            self.cpu_usage.push_front(cpu_usage);
+            push_usage(&mut self.cpu_usage, cpu_usage);
+            self.handle_active_use(cpu_usage);
 
-            let is_paused = self.get_paused();
+            let activity_state = self.get_activity_state(&cpu_mesasure);
 
-            if self.session_status == SessionStatus::Work {
-                if is_paused && self.settings.auto_resume {
-                    if let Some(avg) = cpu_mesasure.resume
-                        && avg >= f32::from(self.settings.auto_resume_threshold)
-                    {
-                        self.sx.send(MsgI::Pause).ok();
-                        self.sx.send(MsgI::ToFrontEnd(MsgFE::GetSettings)).ok();
-                    }
-                } else if !is_paused
-                    && self.settings.auto_pause
-                    && let Some(avg) = cpu_mesasure.pause
-                    && avg <= f32::from(self.settings.auto_pause_threshold)
-                {
-                    self.sx.send(MsgI::Pause).ok();
-                    self.sx.send(MsgI::ToFrontEnd(MsgFE::GetSettings)).ok();
-                }
+            // Activity after a break has ended counts as the user being ready for the next session, regardless of the auto-resume setting
+            if self.flags.awaiting_ready
+                && let Some(avg) = self.calc_cpu_aggregate(READY_ACTIVITY_SEC)
+                && avg >= f32::from(self.settings.auto_resume_threshold)
+            {
+                self.sx.send(MsgI::Break(MsgB::Ready)).ok();
+            }
+
+            let now = now_as_sec();
+            if !self.get_on_break()
+                && let Some(transition) = self.transition_guard.decide(
+                    now,
+                    i64::from(self.activity.dwell_sec),
+                    self.get_pause_reason(),
+                    AutoEnabled {
+                        pause: self.settings.auto_pause,
+                        resume: self.settings.auto_resume,
+                    },
+                    activity_state,
+                )
+            {
+                self.transition_guard.record(transition, now);
+                self.sx
+                    .send(MsgI::SetPaused(
+                        transition == AutoTransition::Pause,
+                        PauseReason::Cpu,
+                    ))
+                    .ok();
             }
             self.sx
                 .send(MsgI::ToFrontEnd(MsgFE::Cpu(cpu_mesasure)))
@@ -395,37 +977,665 @@ impl ApplicationState {
     }
 
     /// Handle all internal messages about the Break/Session stats
-    pub fn handle_break(&mut self, break_message: MsgB) {
+    pub async fn handle_break(&mut self, break_message: MsgB) {
         let fullscreen = self.get_fullscreen();
         match break_message {
             MsgB::Start => {
+                if self.get_on_break() {
+                    return;
+                }
+                // A break started on demand whilst paused would otherwise never count down
+                if self.get_paused() {
+                    let paused = self.toggle_pause(PauseReason::Manual);
+                    self.update_menu_pause(paused);
+                    self.update_icon(paused);
+                    self.sx.send(MsgI::ToFrontEnd(MsgFE::Paused(paused))).ok();
+                }
                 self.start_break_session();
-                change_menu_entry_status(&self.system_tray_menu, false);
-                self.sx.send(MsgI::ToFrontEnd(MsgFE::GoToTimer)).ok();
-                WindowAction::show_window(&self.app_handle, fullscreen);
+                self.show_break(fullscreen).await;
             }
             MsgB::End => {
+                if self.flags.awaiting_ready {
+                    return;
+                }
+                self.active_use.continuous_sec = 0;
+                if self.settings.ready_after_break && self.get_on_break() {
+                    self.flags.awaiting_ready = true;
+                    change_menu_awaiting_ready(&self.system_tray_menu, true);
+                    // The user may not be at the desk, so the window shouldn't keep covering the whole screen
+                    if fullscreen {
+                        WindowAction::remove_fullscreen(&self.app_handle);
+                    }
+                    self.sx.send(MsgI::ToFrontEnd(MsgFE::AwaitingReady)).ok();
+                    return;
+                }
                 self.start_work_session();
-                change_menu_entry_status(&self.system_tray_menu, true);
-                if self.pause_after_break {
-                    self.sx.send(MsgI::Pause).ok();
-                    // if the app is in fullscreen mode, need to remove the fullscreen, normally this is handled by the hide_window function, but it's not being called here
-                    WindowAction::remove_fullscreen(&self.app_handle);
-                } else {
-                    WindowAction::hide_window(&self.app_handle, fullscreen);
-                    MenuManipulation::update_all(self);
+                self.hide_break(fullscreen);
+            }
+            MsgB::Ready => {
+                if self.flags.awaiting_ready {
+                    self.start_work_session();
+                    self.hide_break(fullscreen);
+                }
+            }
+            MsgB::Postpone(minutes) => {
+                let SessionStatus::Break(break_type) = self.session_status else {
+                    return;
+                };
+                if self.flags.awaiting_ready {
+                    return;
+                }
+                if minutes == 0 || self.get_postpones_remaining().await == 0 {
+                    return;
+                }
+                let postpone_sec = u16::from(minutes).saturating_mul(60);
+                self.record_event(ModelEvent {
+                    event_type: EventType::BreakPostponed,
+                    started_at: now_as_sec(),
+                    duration_sec: i64::from(postpone_sec),
+                    detail: Some(break_type.to_string()),
+                });
+                self.postponed_break = Some(break_type);
+                self.change_session(SessionStatus::Work, postpone_sec);
+                self.hide_break(fullscreen);
+            }
+            MsgB::Restore => {
+                if self.get_on_break() {
+                    self.show_break(fullscreen).await;
+                }
+            }
+            MsgB::Skip => {
+                let SessionStatus::Break(break_type) = self.session_status else {
+                    return;
+                };
+                let left_sec = self.get_current_timer_left();
+                if self.settings.carry_over_skipped {
+                    self.carried_break_sec = self.carried_break_sec.saturating_add(left_sec);
+                }
+                self.record_event(ModelEvent {
+                    event_type: EventType::BreakSkipped,
+                    started_at: now_as_sec(),
+                    duration_sec: i64::from(left_sec),
+                    detail: Some(break_type.to_string()),
+                });
+                self.start_work_session();
+                self.hide_break(fullscreen);
+            }
+        }
+    }
+
+    /// Disable the systemtray, and show the break window
+    async fn show_break(&mut self, fullscreen: bool) {
+        self.postpones_remaining = self.get_postpones_remaining().await;
+        change_menu_entry_status(&self.system_tray_menu, false);
+        MenuManipulation::update_upcoming(self);
+        if self.postpones_remaining == 0 {
+            self.get_menu_entry(MenuEntry::Postpone)
+                .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(false).ok()));
+        }
+        self.sx.send(MsgI::ToFrontEnd(MsgFE::GoToTimer)).ok();
+        WindowAction::show_window(&self.app_handle, fullscreen);
+    }
+
+    /// Enable the systemtray, and hide the break window, or pause if `pause_after_break` is set
+    fn hide_break(&mut self, fullscreen: bool) {
+        change_menu_entry_status(&self.system_tray_menu, true);
+        // A break that ends early won't have sent an `OnBreak` of 0, which the frontend uses to leave the timer view
+        self.sx.send(MsgI::ToFrontEnd(MsgFE::OnBreak)).ok();
+        if self.pause_after_break {
+            self.sx
+                .send(MsgI::SetPaused(true, PauseReason::AfterBreak))
+                .ok();
+            // if the app is in fullscreen mode, need to remove the fullscreen, normally this is handled by the hide_window function, but it's not being called here
+            WindowAction::remove_fullscreen(&self.app_handle);
+        } else {
+            WindowAction::hide_window(&self.app_handle, fullscreen);
+            MenuManipulation::update_all(self);
+        }
+        self.pause_after_break = false;
+    }
+
+    /// Show a countdown, next to the systemtray icon & in a small window, during the final `break_warning_sec` of a work session
+    /// The break can then be started now, or delayed, from the systemtray or the window, the window is hidden again once delayed
+    fn handle_break_warning(&mut self, left: u16) {
+        let pre_break = left <= self.settings.break_warning_sec;
+        if !pre_break {
+            self.dismiss_break_warning();
+        }
+        self.update_pre_break(pre_break);
+        if pre_break {
+            set_title(&self.app_handle, Some(format!("break in {left}s")));
+            self.sx
+                .send(MsgI::ToFrontEnd(MsgFE::BreakWarning(left)))
+                .ok();
+        }
+    }
+
+    /// Leave the pre-break phase, and hide its window, used when the break is delayed, or the timer paused
+    fn dismiss_break_warning(&mut self) {
+        if self.flags.pre_break {
+            WindowAction::hide_window(&self.app_handle, false);
+            self.update_pre_break(false);
+        }
+    }
+
+    /// Enter, or leave, the pre-break phase of a work session
+    fn update_pre_break(&mut self, pre_break: bool) {
+        if self.flags.pre_break == pre_break {
+            return;
+        }
+        self.flags.pre_break = pre_break;
+        change_menu_break_warning(&self.system_tray_menu, pre_break && !self.alignment.enabled);
+        if pre_break {
+            WindowAction::show_warning_window(&self.app_handle);
+        } else {
+            set_title(&self.app_handle, None);
+            // A warning of 0 takes the frontend out of the warning view
+            self.sx.send(MsgI::ToFrontEnd(MsgFE::BreakWarning(0))).ok();
+        }
+    }
+
+    /// Handle all internal messages about activity signals, the options are then sent to the frontend
+    pub async fn handle_activity(&mut self, activity_message: MsgAC) -> Result<(), AppError> {
+        let activity = match activity_message {
+            MsgAC::Get => ModelActivity::get(&self.sqlite).await?,
+            MsgAC::Set(activity) => {
+                ModelActivity::update(&self.sqlite, &activity).await?;
+                activity
+            }
+        };
+        // The cpu usage history is only comparable when measured the same way
+        if activity.cpu_mode != self.activity.cpu_mode
+            || activity.cpu_processes != self.activity.cpu_processes
+        {
+            self.cpu_usage.clear();
+            heartbeat_process(&self.sx, activity.cpu_mode, activity.cpu_processes.clone());
+        }
+        self.activity = activity;
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Activity(self.activity.clone())))
+            .ok();
+        Ok(())
+    }
+
+    /// Handle all internal messages about clock-aligned sessions, the options are then sent to the frontend
+    /// Changing the options during a work session restarts it, so that it's aligned to the new options
+    pub async fn handle_alignment(&mut self, alignment_message: MsgAL) -> Result<(), AppError> {
+        match alignment_message {
+            MsgAL::Get => self.alignment = ModelAlignment::get(&self.sqlite).await?,
+            MsgAL::Set(alignment) => {
+                ModelAlignment::update(&self.sqlite, &alignment).await?;
+                self.alignment = alignment;
+                if !self.get_on_break() {
+                    self.reset_timer();
+                    self.update_menu_all();
+                }
+            }
+        }
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Alignment(self.alignment)))
+            .ok();
+        Ok(())
+    }
+
+    /// Handle all internal messages about flow mode, the options are then sent to the frontend
+    /// Changing mode during a work session ends it, and starts a session of the new mode
+    pub async fn handle_flow(&mut self, flow_message: MsgFL) -> Result<(), AppError> {
+        match flow_message {
+            MsgFL::Get => self.flow = ModelFlow::get(&self.sqlite).await?,
+            MsgFL::Set(flow) => {
+                ModelFlow::update(&self.sqlite, &flow).await?;
+                self.flow = flow;
+                let session_status = self.get_work_status();
+                if !self.get_on_break() && self.session_status != session_status {
+                    self.change_session(session_status, self.get_planned_sec(session_status));
+                    self.update_menu_all();
+                }
+            }
+        }
+        self.sx.send(MsgI::ToFrontEnd(MsgFE::Flow(self.flow))).ok();
+        Ok(())
+    }
+
+    /// Handle all internal messages about interruptions, the interruptions of the current session are then sent to the frontend
+    /// Interruptions can only be logged during a work session
+    pub async fn handle_interruption(&self, interruption_message: MsgIR) -> Result<(), AppError> {
+        let session_started_at = self.session_record.started_at;
+        if let MsgIR::Record(interruption_type, note) = interruption_message {
+            if self.get_on_break() {
+                return Err(AppError::Internal(
+                    "Unable to log an interruption during a break".to_owned(),
+                ));
+            }
+            let interruption = ModelInterruption {
+                session_started_at,
+                interruption_type,
+                note: note.map(|i| i.trim().to_owned()).filter(|i| !i.is_empty()),
+                created_at: now_as_sec(),
+            };
+            ModelInterruption::insert(&self.sqlite, &interruption).await?;
+        }
+        let interruptions =
+            ModelInterruption::get_session(&self.sqlite, session_started_at).await?;
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Interruptions(interruptions)))
+            .ok();
+        Ok(())
+    }
+
+    /// Handle all internal messages about profiles, the updated profiles are then sent to the systemtray & frontend
+    pub async fn handle_profile(&mut self, profile_message: MsgP) -> Result<(), AppError> {
+        let sqlite = self.sqlite.clone();
+        match profile_message {
+            MsgP::Activate(profile_id) => {
+                if self.get_on_break() {
+                    return Ok(());
+                }
+                let Some(profile) = ModelProfile::get(&sqlite, profile_id).await? else {
+                    return Err(AppError::Internal(format!("Unknown profile {profile_id}")));
+                };
+                let settings = profile.apply(self.settings);
+                ModelSettings::update(&sqlite, &settings).await?;
+                ModelProfile::set_active(&sqlite, profile_id).await?;
+                let session_changed = settings.session_as_sec != self.settings.session_as_sec;
+                self.settings = settings;
+                self.update_cycle().await?;
+                if session_changed || !self.cycle.is_empty() {
+                    self.reset_timer();
+                }
+                self.sx.send(MsgI::ToFrontEnd(MsgFE::GetSettings)).ok();
+                self.update_menu_all();
+            }
+            MsgP::Create(name) => {
+                ModelProfile::create(&sqlite, &name, &self.settings).await?;
+                // The new, and now active, profile has the same settings, but no custom cycle, a break in progress isn't restarted
+                let had_cycle = !self.cycle.is_empty();
+                self.update_cycle().await?;
+                if had_cycle && !self.get_on_break() {
+                    self.reset_timer();
                 }
-                self.pause_after_break = false;
+                self.update_menu_all();
+            }
+            MsgP::Delete(profile_id) => ModelProfile::delete(&sqlite, profile_id).await?,
+            MsgP::Get => (),
+            MsgP::GetCycle => return self.update_cycle().await,
+            MsgP::Rename(profile_id, name) => {
+                ModelProfile::rename(&sqlite, profile_id, &name).await?;
+            }
+            MsgP::SetCycle(segments) => {
+                ModelCycleSegment::set_active(&sqlite, &segments).await?;
+                self.update_cycle().await?;
+                self.save_timer_state();
+                self.update_menu_all();
+                return Ok(());
             }
         }
+        self.update_profiles().await
     }
 
-    /// Handle all internal messages about window visibility
-    pub fn handle_visibility(&self, window_visibility: MsgWV) {
+    /// Handle all internal messages about the working hours schedule, the schedule is sent to the frontend
+    pub async fn handle_schedule(&mut self, schedule_message: MsgWH) -> Result<(), AppError> {
+        match schedule_message {
+            MsgWH::Get => self.schedule = ModelSchedule::get(&self.sqlite).await?,
+            MsgWH::Set(schedule) => {
+                ModelSchedule::set(&self.sqlite, &schedule).await?;
+                self.schedule = schedule;
+                self.working_hours = None;
+            }
+        }
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Schedule(self.schedule.clone())))
+            .ok();
+        Ok(())
+    }
+
+    /// Handle all internal messages about recurring reminders, the reminders are sent to the frontend
+    /// Replacing the reminders restarts the countdown of every reminder
+    pub async fn handle_reminder(&mut self, reminder_message: MsgRM) -> Result<(), AppError> {
+        if let MsgRM::Set(reminders) = reminder_message {
+            ModelReminder::set_all(&self.sqlite, &reminders).await?;
+        }
+        let reminders = ModelReminder::get_all(&self.sqlite).await?;
+        self.reminders = reminders
+            .iter()
+            .map(|reminder| ReminderTimer {
+                reminder: reminder.clone(),
+                elapsed_sec: 0,
+            })
+            .collect();
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Reminders(reminders)))
+            .ok();
+        Ok(())
+    }
+
+    /// Count down every reminder inside its active hours, once a second, and show any that are due
+    /// Reminders don't count down during a break, nor whilst the timer is paused if set to pause with the timer
+    fn handle_reminders(&mut self) {
+        if self
+            .reminder_title_until
+            .is_some_and(|until| until <= Instant::now())
+        {
+            self.reminder_title_until = None;
+            if !self.flags.pre_break {
+                set_title(&self.app_handle, None);
+            }
+        }
+        if self
+            .reminder_overlay_until
+            .is_some_and(|until| until <= Instant::now())
+        {
+            self.reminder_overlay_until = None;
+            // The window has since been taken over by the break warning, or the break itself
+            if !self.flags.pre_break && !self.get_on_break() && !self.flags.awaiting_ready {
+                WindowAction::hide_window(&self.app_handle, false);
+            }
+        }
+        if self.get_on_break() {
+            return;
+        }
+        if !self.flags.pre_break
+            && self.reminder_title_until.is_none()
+            && self.reminder_overlay_until.is_none()
+            && let Some(reminder) = self.queued_reminders.pop_front()
+        {
+            self.show_reminder(reminder);
+        }
+        let now = chrono::Local::now();
+        let minute = u16::try_from(now.hour() * 60 + now.minute()).unwrap_or_default();
+        let paused = self.get_paused();
+        let mut due = vec![];
+        for timer in &mut self.reminders {
+            if !timer.reminder.is_active(minute) || (paused && timer.reminder.pause_with_timer) {
+                continue;
+            }
+            timer.elapsed_sec = timer.elapsed_sec.saturating_add(1);
+            if timer.elapsed_sec >= timer.reminder.interval_sec {
+                timer.elapsed_sec = 0;
+                due.push(timer.reminder.clone());
+            }
+        }
+        for reminder in due {
+            self.show_reminder(reminder);
+        }
+    }
+
+    /// Show a due reminder for its duration, a notification is shown next to the systemtray icon, and an overlay shows the main window
+    /// The break warning already uses both, so a reminder due during it is queued until the warning has ended
+    fn show_reminder(&mut self, mut reminder: ModelReminder) {
+        if self.flags.pre_break {
+            self.queued_reminders.push_back(reminder);
+            return;
+        }
+        // The frontend dismisses an overlay after `duration_sec`, so it needs the duration actually used
+        reminder.duration_sec = reminder.duration_sec.max(REMINDER_MIN_SHOW_SEC);
+        let until =
+            Instant::now() + std::time::Duration::from_secs(u64::from(reminder.duration_sec));
+        match reminder.display_mode {
+            ReminderDisplay::Notification => {
+                set_title(&self.app_handle, Some(reminder.label.clone()));
+                self.reminder_title_until = Some(until);
+            }
+            ReminderDisplay::Overlay => {
+                WindowAction::show_window(&self.app_handle, false);
+                self.reminder_overlay_until = Some(until);
+            }
+        }
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Reminder(reminder)))
+            .ok();
+    }
+
+    /// Handle all internal messages about resume behaviours, the behaviours are sent to the frontend
+    pub async fn handle_resume_behaviour(
+        &mut self,
+        resume_behaviour_message: MsgRB,
+    ) -> Result<(), AppError> {
+        if let MsgRB::Set(behaviour) = resume_behaviour_message {
+            ModelResumeBehaviour::set(&self.sqlite, &behaviour).await?;
+        }
+        self.resume_behaviours = ModelResumeBehaviour::get_all(&self.sqlite).await?;
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::ResumeBehaviours(
+                self.resume_behaviours.clone(),
+            )))
+            .ok();
+        Ok(())
+    }
+
+    /// Track continuous, and daily, active use, regardless of whether the timer is paused
+    /// Once continuous use reaches the overwork limit a break is forced, and once daily use reaches the daily cap the timer is paused until the next day
+    /// Being idle for at least a short break resets the continuous use, the daily use is saved to SQLite every minute of activity
+    fn handle_active_use(&mut self, cpu_usage: f32) {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        if self.active_use.day != today {
+            self.active_use.day = today;
+            self.active_use.today_sec = 0;
+            self.update_end_of_day(false);
+        }
+        if cpu_usage >= f32::from(self.settings.auto_resume_threshold) {
+            self.active_use.idle_sec = 0;
+            self.active_use.continuous_sec = self.active_use.continuous_sec.saturating_add(1);
+            self.active_use.today_sec = self.active_use.today_sec.saturating_add(1);
+            if self.active_use.today_sec.is_multiple_of(60) {
+                self.save_active_use();
+            }
+        } else {
+            self.active_use.idle_sec = self.active_use.idle_sec.saturating_add(1);
+            if self.active_use.idle_sec >= self.settings.short_break_as_sec {
+                self.active_use.continuous_sec = 0;
+            }
+        }
+
+        if self.settings.overwork_limit_sec > 0
+            && self.active_use.continuous_sec >= u32::from(self.settings.overwork_limit_sec)
+            && !self.get_on_break()
+        {
+            self.record_event(ModelEvent {
+                event_type: EventType::Overwork,
+                started_at: now_as_sec().saturating_sub(i64::from(self.active_use.continuous_sec)),
+                duration_sec: i64::from(self.active_use.continuous_sec),
+                detail: None,
+            });
+            self.active_use.continuous_sec = 0;
+            self.sx.send(MsgI::Break(MsgB::Start)).ok();
+        }
+
+        if self.settings.daily_cap_sec > 0
+            && self.active_use.today_sec >= self.settings.daily_cap_sec
+            && !self.flags.end_of_day
+        {
+            self.record_event(ModelEvent {
+                event_type: EventType::EndOfDay,
+                started_at: now_as_sec(),
+                duration_sec: i64::from(self.active_use.today_sec),
+                detail: None,
+            });
+            self.update_end_of_day(true);
+        }
+    }
+
+    /// Enter, or leave, the end of day mode, the timer is paused on entering, and resumed on leaving if it is still paused for the end of day
+    fn update_end_of_day(&mut self, end_of_day: bool) {
+        if self.flags.end_of_day == end_of_day {
+            return;
+        }
+        self.flags.end_of_day = end_of_day;
+        self.update_icon(self.get_paused());
+        self.sx
+            .send(MsgI::SetPaused(end_of_day, PauseReason::EndOfDay))
+            .ok();
+    }
+
+    /// Load the active use of the current day from SQLite, so that the daily cap survives a restart
+    pub async fn restore_active_use(&mut self) -> Result<(), AppError> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        self.active_use.today_sec = ModelActiveDay::get(&self.sqlite, &today).await?;
+        self.active_use.day = today;
+        Ok(())
+    }
+
+    /// Save the active use of the current day into SQLite, in its own tokio thread
+    fn save_active_use(&self) {
+        let (sqlite, day, active_sec) = (
+            self.sqlite.clone(),
+            self.active_use.day.clone(),
+            self.active_use.today_sec,
+        );
+        tokio::spawn(async move {
+            if let Err(e) = ModelActiveDay::upsert(&sqlite, &day, active_sec).await {
+                tracing::error!("{:#?}", e);
+            }
+        });
+    }
+
+    /// Store a finished minute of activity history, and remove any history older than the retention limit
+    fn save_activity_history(&self, history: ModelActivityHistory) {
+        let (sqlite, retention_days) = (self.sqlite.clone(), self.activity.history_retention_days);
+        tokio::spawn(async move {
+            if let Err(e) = ModelActivityHistory::insert(&sqlite, &history, retention_days).await {
+                tracing::error!("{:#?}", e);
+            }
+        });
+    }
+
+    /// Resume the timer once a timed pause has ended
+    /// Resumed directly, rather than with a message, so that the following heartbeats see that the pause has already ended
+    fn handle_pause_expiry(&mut self) {
+        if let Some(resume_at) = self.get_resume_at()
+            && now_as_sec() >= resume_at
+        {
+            self.set_paused(false, PauseReason::Manual);
+        }
+    }
+
+    /// Handle all internal messages about process watches, the process watches are sent to the frontend
+    pub async fn handle_process_watch(
+        &mut self,
+        process_watch_message: MsgPW,
+    ) -> Result<(), AppError> {
+        if let MsgPW::Set(process_watches) = process_watch_message {
+            ModelProcessWatch::set_all(&self.sqlite, &process_watches).await?;
+        }
+        let process_watches = ModelProcessWatch::get_all(&self.sqlite).await?;
+        self.process_patterns = process_watches
+            .iter()
+            .filter(|i| i.enabled)
+            .map(ModelProcessWatch::to_regex)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::ProcessWatches(process_watches)))
+            .ok();
+        Ok(())
+    }
+
+    /// Pause the timer when a watched process starts running, and resume it, if it was paused by a process, once none are running
+    /// Only a change between a watched process running & not running is acted upon, so the timer can still be manually resumed, or paused
+    /// A change during a break is acted upon once the break has ended
+    pub fn handle_processes(&mut self, names: &[String]) {
+        if self.get_on_break() {
+            return;
+        }
+        let running = names
+            .iter()
+            .find(|name| self.process_patterns.iter().any(|i| i.is_match(name)))
+            .cloned();
+        let changed = running.is_some() != self.watched_process.is_some();
+        self.watched_process = running;
+        if !changed {
+            return;
+        }
+        self.sx
+            .send(MsgI::SetPaused(
+                self.watched_process.is_some(),
+                PauseReason::Process,
+            ))
+            .ok();
+    }
+
+    /// Pause the timer when working hours end, and resume it, if it was paused by the schedule, when they start again
+    /// Only a change between inside & outside working hours is acted upon, so the timer can still be manually resumed, or paused
+    /// A change during a break is acted upon once the break has ended
+    fn handle_working_hours(&mut self) {
+        if self.get_on_break() {
+            return;
+        }
+        let now = chrono::Local::now();
+        let working = self.schedule.is_working(
+            u8::try_from(now.weekday().num_days_from_sunday()).unwrap_or_default(),
+            u16::try_from(now.hour() * 60 + now.minute()).unwrap_or_default(),
+            &now.format("%Y-%m-%d").to_string(),
+        );
+        if self.working_hours == Some(working) {
+            return;
+        }
+        self.working_hours = Some(working);
+        self.sx
+            .send(MsgI::SetPaused(!working, PauseReason::Schedule))
+            .ok();
+    }
+
+    /// Handle all internal messages requesting stats, the results of the SQLite queries are sent to the frontend
+    pub async fn handle_stats(&self, stats_message: MsgS) -> Result<(), AppError> {
+        let msg = match stats_message {
+            MsgS::ActivityHistory(from, to) => MsgFE::ActivityHistory(
+                ModelActivityHistory::get_range(&self.sqlite, from, to).await?,
+            ),
+            MsgS::Heatmap => MsgFE::StatsHeatmap(ModelStats::get_heatmap(&self.sqlite).await?),
+            MsgS::Totals(period) => {
+                MsgFE::StatsTotals(ModelStats::get_totals(&self.sqlite, period).await?)
+            }
+        };
+        self.sx.send(MsgI::ToFrontEnd(msg)).ok();
+        Ok(())
+    }
+
+    /// Apply the suspend policy after the system has been suspended, and record the suspension as an event, and in the pause history
+    /// Any time `Instant` advanced whilst suspended is first removed from a running timer, so `Resume` continues from where the timer was
+    pub fn handle_suspension(&mut self, suspension: Suspension) {
+        if let Timer::Work(original) = self.timer {
+            self.timer = Timer::Work(original + suspension.instant);
+        }
+        let suspended_sec = suspension.wall.as_secs();
+        match self.settings.suspend_policy {
+            SuspendPolicy::BreakTaken => {
+                if suspended_sec >= u64::from(self.settings.short_break_as_sec) {
+                    if self.get_on_break() {
+                        self.sx.send(MsgI::Break(MsgB::End)).ok();
+                    } else {
+                        if suspended_sec >= u64::from(self.settings.long_break_as_sec) {
+                            self.session_count = 0;
+                            self.cycle_index = 0;
+                        }
+                        self.reset_timer();
+                    }
+                }
+            }
+            SuspendPolicy::Restart => self.reset_timer(),
+            SuspendPolicy::Resume => (),
+        }
+        self.update_menu_all();
+        self.record_event(ModelEvent {
+            event_type: EventType::Suspend,
+            started_at: now_as_sec()
+                .saturating_sub(i64::try_from(suspended_sec).unwrap_or_default()),
+            duration_sec: i64::try_from(suspended_sec).unwrap_or_default(),
+            detail: Some(self.settings.suspend_policy.to_string()),
+        });
+        self.record_pause(PauseReason::Suspend, suspended_sec, None);
+    }
+
+    /// Handle all internal messages about window visibility, the timer state is saved before closing
+    pub async fn handle_visibility(&self, window_visibility: MsgWV) {
         let on_break = self.get_on_break();
         match window_visibility {
             MsgWV::Close => {
                 if !on_break {
+                    if let Err(e) =
+                        ModelTimerState::upsert(&self.sqlite, &self.get_timer_state()).await
+                    {
+                        tracing::error!("{:#?}", e);
+                    }
                     self.app_handle.exit(0);
                 }
             }
@@ -463,12 +1673,23 @@ impl ApplicationState {
         }
     }
 
-    /// Auto Pause/Resume, send timer stats
-    pub fn on_heartbeat(&mut self, cpu_usage: Option<f32>) {
-        self.handle_auto_pause_resume(cpu_usage);
+    /// Auto Pause/Resume, count down reminders, send timer stats, periodically save the timer state
+    pub fn on_heartbeat(&mut self, activity: Option<ActivitySample>) {
+        // Activity is only sampled once a second
+        if activity.is_some() {
+            self.handle_reminders();
+        }
+        self.handle_auto_pause_resume(activity);
+        self.handle_working_hours();
+        self.handle_pause_expiry();
 
         if !self.get_paused() {
+            if self.snapshot_time.elapsed().as_secs() >= SNAPSHOT_INTERVAL_SEC {
+                self.save_timer_state();
+            }
             match self.session_status {
+                // Whilst waiting for the user to be ready, an `OnBreak` of 0 would take the frontend out of the break view
+                SessionStatus::Break(_) if self.flags.awaiting_ready => (),
                 SessionStatus::Break(_) => {
                     self.sx.send(MsgI::ToFrontEnd(MsgFE::OnBreak)).ok();
                     if self.get_current_timer_left() < 1 {
@@ -477,10 +1698,16 @@ impl ApplicationState {
                 }
                 SessionStatus::Work => {
                     self.sx.send(MsgI::UpdateMenuTimer).ok();
-                    if self.get_current_timer_left() < 1 {
+                    let left = self.get_current_timer_left();
+                    if left < 1 {
                         self.sx.send(MsgI::Break(MsgB::Start)).ok();
+                    } else {
+                        self.handle_break_warning(left);
                     }
                 }
+                SessionStatus::Flow => {
+                    self.sx.send(MsgI::UpdateMenuTimer).ok();
+                }
             }
         }
     }
@@ -491,6 +1718,7 @@ impl ApplicationState {
     pub async fn reset_settings(&mut self) -> Result<(), AppError> {
         let sqlite = self.sqlite.clone();
         let settings = ModelSettings::reset_settings(&sqlite).await?;
+        ModelProfile::update_active(&sqlite, &settings).await?;
         self.set_settings(settings);
         self.reset_timer();
         self.sx.send(MsgI::ToFrontEnd(MsgFE::GetSettings)).ok();
@@ -500,8 +1728,150 @@ impl ApplicationState {
         Ok(())
     }
 
+    /// Restore the timer from a snapshot, as long as the snapshot isn't older than the `restore_max_age_sec` setting
+    /// Without a usable snapshot, the first work session is started, which will be the first segment of a custom cycle
+    pub fn restore_timer_state(&mut self, timer_state: Option<ModelTimerState>) {
+        let Some(timer_state) = timer_state.filter(|timer_state| {
+            let age = now_as_sec().saturating_sub(timer_state.updated_at);
+            self.settings.restore_max_age_sec != 0
+                && age >= 0
+                && age <= i64::from(self.settings.restore_max_age_sec)
+        }) else {
+            self.session_status = self.get_work_status();
+            self.session_record =
+                SessionRecord::new(self.get_planned_sec(self.session_status), false);
+            return;
+        };
+        let now = std::time::Instant::now();
+        let taken = std::time::Duration::from_secs(u64::from(
+            timer_state
+                .planned_sec
+                .saturating_sub(timer_state.remaining_sec),
+        ));
+        let original = now.checked_sub(taken).unwrap_or(now);
+
+        self.pause_after_break = timer_state.pause_after_break;
+        self.session_count = timer_state.session_count;
+        if usize::from(timer_state.cycle_index) < self.cycle.len() {
+            self.cycle_index = timer_state.cycle_index;
+        }
+        self.session_status = match SessionStatus::from(timer_state.session_type) {
+            SessionStatus::Work => self.get_work_status(),
+            session_status => session_status,
+        };
+        self.session_record = SessionRecord {
+            planned_sec: timer_state.planned_sec,
+            paused: timer_state.paused,
+            interrupted: false,
+            started_at: timer_state.session_started_at,
+        };
+        self.timer = if timer_state.paused {
+            Timer::Paused {
+                original,
+                paused: now,
+                reason: timer_state.pause_reason.unwrap_or(PauseReason::Manual),
+                resume_at: timer_state.resume_at,
+            }
+        } else {
+            Timer::Work(original)
+        };
+        self.update_menu_pause(timer_state.paused);
+        self.update_icon(timer_state.paused);
+    }
+
+    /// Reset the current timer, the session so far is recorded as cut short
     pub fn reset_timer(&mut self) {
+        self.change_session(
+            self.session_status,
+            self.get_planned_sec(self.session_status),
+        );
+    }
+
+    /// Record the session that has just ended, and reset the timer for the new session status, which will last for `planned_sec`
+    fn change_session(&mut self, session_status: SessionStatus, planned_sec: u16) {
+        self.record_session();
+        self.update_pre_break(false);
+        self.flags.awaiting_ready = false;
+        self.session_status = session_status;
+        self.session_record = SessionRecord::new(planned_sec, self.get_paused());
         self.timer = self.timer.reset();
+        self.save_timer_state();
+    }
+
+    /// Save a snapshot of the timer state into SQLite, in its own tokio thread
+    fn save_timer_state(&mut self) {
+        self.snapshot_time = std::time::Instant::now();
+        let (sqlite, timer_state) = (self.sqlite.clone(), self.get_timer_state());
+        tokio::spawn(async move {
+            if let Err(e) = ModelTimerState::upsert(&sqlite, &timer_state).await {
+                tracing::error!("{:#?}", e);
+            }
+        });
+    }
+
+    /// Insert an event into the event table, in its own tokio thread
+    fn record_event(&self, event: ModelEvent) {
+        let sqlite = self.sqlite.clone();
+        tokio::spawn(async move {
+            if let Err(e) = ModelEvent::insert(&sqlite, &event).await {
+                tracing::error!("{:#?}", e);
+            }
+        });
+    }
+
+    /// Insert a pause, which ended `duration_sec` seconds after it started, into the pause history, in its own tokio thread
+    fn record_pause(
+        &self,
+        pause_reason: PauseReason,
+        duration_sec: u64,
+        resume_behaviour: Option<ResumeBehaviour>,
+    ) {
+        let duration_sec = i64::try_from(duration_sec).unwrap_or_default();
+        let pause = ModelPause {
+            pause_reason,
+            started_at: now_as_sec().saturating_sub(duration_sec),
+            duration_sec,
+            resume_behaviour,
+        };
+        let sqlite = self.sqlite.clone();
+        tokio::spawn(async move {
+            if let Err(e) = ModelPause::insert(&sqlite, &pause).await {
+                tracing::error!("{:#?}", e);
+            }
+        });
+    }
+
+    /// Insert the current session into the stats table, in its own tokio thread
+    /// Time spent waiting for the user to be ready, after a break has ended, is included in the break
+    fn record_session(&self) {
+        let (planned_sec, left_sec) = self.get_session_progress();
+        let actual_sec = if self.flags.awaiting_ready {
+            self.get_current_timer_elapsed()
+        } else {
+            planned_sec.saturating_sub(left_sec)
+        };
+        let stats = SessionRecord {
+            planned_sec,
+            ..self.session_record
+        }
+        .finish(self.session_status, actual_sec);
+        if stats.actual_sec == 0 {
+            return;
+        }
+        self.insert_stats(stats);
+    }
+
+    /// Insert a finished session into the stats table, in its own tokio thread
+    fn insert_stats(&self, stats: ModelStats) {
+        let (sqlite, sx) = (self.sqlite.clone(), self.sx.clone());
+        tokio::spawn(async move {
+            match ModelStats::insert(&sqlite, &stats).await {
+                Ok(()) => {
+                    sx.send(MsgI::ToFrontEnd(MsgFE::SessionEnded)).ok();
+                }
+                Err(e) => tracing::error!("{:#?}", e),
+            }
+        });
     }
 
     /// Send an internal message
@@ -515,35 +1885,203 @@ impl ApplicationState {
         Self::get_auto_launch().and_then(|i| i.disable().ok());
     }
 
-    /// Start the break session
-    /// TODO update db session count
+    /// Start the break session, the work session that just ended is recorded in the stats table
+    /// A postponed break keeps its original break type, and any carried over time from a skipped break is added on
+    /// A break after a flow session lasts for the flow break percentage of the work done
     pub fn start_break_session(&mut self) {
-        let break_type = if self.session_count + 1 < self.settings.number_session_before_break {
-            self.session_count += 1;
-            BreakVariant::Short
+        let flow_break_sec = (self.session_status == SessionStatus::Flow)
+            .then(|| self.flow.calc_break_sec(self.get_current_timer_elapsed()));
+        let session_status = if let Some(postponed) = self.postponed_break.take() {
+            SessionStatus::Break(postponed)
+        } else if self.cycle.is_empty() {
+            let break_type = self.get_next_break_variant();
+            match break_type {
+                BreakVariant::Short => self.session_count += 1,
+                BreakVariant::Long => self.session_count = 0,
+            }
+            SessionStatus::Break(break_type)
         } else {
-            self.session_count = 0;
-            BreakVariant::Long
+            self.cycle_index = ModelCycleSegment::next_index(&self.cycle, self.cycle_index);
+            self.get_cycle_segment()
+                .map_or(SessionStatus::Break(BreakVariant::Short), |segment| {
+                    SessionStatus::from(segment.segment_type)
+                })
         };
-        self.reset_timer();
-        self.session_status = SessionStatus::Break(break_type);
+        let planned_sec = flow_break_sec
+            .unwrap_or_else(|| self.get_planned_sec(session_status))
+            .saturating_add(std::mem::take(&mut self.carried_break_sec));
+        self.change_session(session_status, planned_sec);
     }
 
-    /// Start the timer, by saetting the next_break_in value
+    /// Start the timer, by saetting the next_break_in value, the break that just ended is recorded in the stats table
     pub fn start_work_session(&mut self) {
-        self.session_status = SessionStatus::Work;
-        self.reset_timer();
+        self.cycle_index = ModelCycleSegment::next_index(&self.cycle, self.cycle_index);
+        let session_status = self.get_work_status();
+        self.change_session(session_status, self.get_planned_sec(session_status));
+    }
+
+    /// Pause the timer, or change an existing pause, so that it automatically resumes at the given time, return true if paused
+    /// Can't be used whilst on a break
+    pub fn pause_until(&mut self, until: PauseUntil) -> bool {
+        if self.get_on_break() {
+            return false;
+        }
+        let now = chrono::Local::now();
+        let resume_at = match until {
+            PauseUntil::Minutes(minutes) => {
+                Some(now + chrono::Duration::minutes(i64::from(minutes)))
+            }
+            PauseUntil::Time { hour, minute } => {
+                chrono::NaiveTime::from_hms_opt(u32::from(hour), u32::from(minute), 0).and_then(
+                    |time| {
+                        let today = now.date_naive().and_time(time);
+                        let next = if today > now.naive_local() {
+                            today
+                        } else {
+                            today + chrono::Duration::days(1)
+                        };
+                        next.and_local_timezone(chrono::Local).earliest()
+                    },
+                )
+            }
+            PauseUntil::NextWorkingDay => self
+                .schedule
+                .get_next_working_day(now.date_naive())
+                .map_or(Some(now + chrono::Duration::days(1)), |next| {
+                    next.and_local_timezone(chrono::Local).earliest()
+                }),
+        };
+        let Some(resume_at) = resume_at else {
+            tracing::error!("Invalid pause until {until:?}");
+            return false;
+        };
+        self.timer = self.timer.pause_until(resume_at.timestamp());
+        self.session_record.paused = true;
+        self.dismiss_break_warning();
+        self.save_timer_state();
+        true
     }
 
-    /// Toggle the pause status & return the pause status
-    pub fn toggle_pause(&mut self) -> bool {
-        self.timer = self.timer.toggle();
+    /// Get the natural break, and the time away, of a pause that was long enough to count as a break
+    /// Only manual, auto, and meeting, pauses are credited, and only up to `NATURAL_BREAK_MAX_SEC`, the other reasons are time away from work, rather than a break
+    /// An auto-pause also includes the idle time before the timer was paused
+    fn get_natural_break(
+        &self,
+        reason: PauseReason,
+        pause_sec: u64,
+    ) -> Option<(BreakVariant, u64)> {
+        let away_sec = match reason {
+            PauseReason::Cpu => {
+                pause_sec.saturating_add(u64::from(self.settings.auto_pause_timespan_sec))
+            }
+            PauseReason::Manual | PauseReason::Meeting => pause_sec,
+            PauseReason::Schedule
+            | PauseReason::AfterBreak
+            | PauseReason::EndOfDay
+            | PauseReason::Process
+            | PauseReason::Suspend => return None,
+        };
+        if away_sec > NATURAL_BREAK_MAX_SEC {
+            None
+        } else if away_sec >= u64::from(self.settings.long_break_as_sec) {
+            Some((BreakVariant::Long, away_sec))
+        } else if away_sec >= u64::from(self.settings.short_break_as_sec) {
+            Some((BreakVariant::Short, away_sec))
+        } else {
+            None
+        }
+    }
+
+    /// Credit a natural break, a long break resets the long break count, and the break is recorded in the stats table
+    /// If the timer was paused whilst on a break, the break is ended instead
+    fn credit_natural_break(&mut self, break_type: BreakVariant, away_sec: u64) {
+        if self.get_on_break() {
+            self.sx.send(MsgI::Break(MsgB::End)).ok();
+            return;
+        }
+        if break_type == BreakVariant::Long {
+            self.session_count = 0;
+            self.cycle_index = 0;
+        }
+        self.active_use.continuous_sec = 0;
+        let session_status = SessionStatus::Break(break_type);
+        let ended_at = now_as_sec();
+        self.insert_stats(ModelStats {
+            session_type: SessionType::from(session_status),
+            started_at: ended_at.saturating_sub(i64::try_from(away_sec).unwrap_or_default()),
+            ended_at,
+            planned_sec: self.get_planned_sec(session_status),
+            actual_sec: u32::try_from(away_sec).unwrap_or(u32::MAX),
+            outcome: SessionOutcome::Natural,
+        });
+        self.update_menu_all();
+    }
+
+    /// Toggle the pause status & return the pause status, the reason is only used when pausing
+    /// When resuming, the pause is recorded, and the resume behaviour of its reason is applied to the current session
+    /// A clock-aligned work session that should have ended during the pause is restarted, so that it runs until the next aligned break
+    /// A pause long enough to count as a break is credited as a natural break, and the work session is restarted
+    pub fn toggle_pause(&mut self, reason: PauseReason) -> bool {
+        let ended_pause = match self.timer {
+            Timer::Paused { paused, reason, .. } => Some((reason, paused.elapsed().as_secs())),
+            Timer::Work(_) => None,
+        };
+        self.timer = self.timer.toggle(reason);
+        if let Some((reason, pause_sec)) = ended_pause {
+            let behaviour = ModelResumeBehaviour::get_behaviour(&self.resume_behaviours, reason);
+            self.record_pause(reason, pause_sec, Some(behaviour));
+            let natural_break = self.get_natural_break(reason, pause_sec);
+            match behaviour {
+                ResumeBehaviour::Continue => {
+                    if (natural_break.is_some() || self.get_aligned_left() == Some(0))
+                        && !self.get_on_break()
+                    {
+                        self.reset_timer();
+                    }
+                }
+                ResumeBehaviour::Restart => self.reset_timer(),
+                ResumeBehaviour::Discard => {
+                    self.session_record.interrupted = true;
+                    self.reset_timer();
+                }
+            }
+            if let Some((break_type, away_sec)) = natural_break {
+                self.credit_natural_break(break_type, away_sec);
+            }
+        } else {
+            self.session_record.paused = true;
+            self.dismiss_break_warning();
+        }
+        self.save_timer_state();
         self.get_paused()
     }
 
+    /// Pause, or resume, the timer, unless it's already in that state, and update the systemtray & frontend
+    /// A resume only ends a pause with the same reason, so one source of pauses can't end a pause made by another
+    pub fn set_paused(&mut self, paused: bool, reason: PauseReason) {
+        let change = if paused {
+            !self.get_paused()
+        } else {
+            self.get_pause_reason() == Some(reason)
+        };
+        if change {
+            let paused = self.toggle_pause(reason);
+            self.update_paused(paused);
+            self.sx.send(MsgI::ToFrontEnd(MsgFE::GetSettings)).ok();
+        }
+    }
+
+    /// Update the systemtray & frontend after the timer has been paused or resumed
+    pub fn update_paused(&self, paused: bool) {
+        self.update_menu_pause(paused);
+        self.update_icon(paused);
+        self.sx.send(MsgI::ToFrontEnd(MsgFE::Paused(paused))).ok();
+    }
+
     /// Update the pause_after_break value
-    pub const fn update_pause_after_break(&mut self, pause: bool) {
+    pub fn update_pause_after_break(&mut self, pause: bool) {
         self.pause_after_break = pause;
+        self.save_timer_state();
     }
 
     /// Update all the settings
@@ -561,13 +2099,40 @@ impl ApplicationState {
     }
 
     pub fn update_icon(&self, paused: bool) {
-        set_icon(&self.app_handle, paused);
+        set_icon(&self.app_handle, paused, self.flags.end_of_day);
     }
 
     pub fn update_menu_all(&self) {
         MenuManipulation::update_all(self);
     }
 
+    /// Load the cycle of the active profile, and send it to the frontend
+    /// The cycle is restarted, from the segment matching the current session, so the current session isn't interrupted
+    pub async fn update_cycle(&mut self) -> Result<(), AppError> {
+        self.cycle = ModelCycleSegment::get_active(&self.sqlite).await?;
+        self.cycle_index = if self.get_on_break() {
+            u8::try_from(self.cycle.len().saturating_sub(1)).unwrap_or_default()
+        } else {
+            0
+        };
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Cycle(self.cycle.clone())))
+            .ok();
+        Ok(())
+    }
+
+    /// Rebuild the systemtray `Profiles` submenu, and send all profiles to the frontend
+    pub async fn update_profiles(&self) -> Result<(), AppError> {
+        let profiles = ModelProfile::get_all(&self.sqlite).await?;
+        if let Err(e) = set_profiles(&self.app_handle, &self.system_tray_menu, &profiles) {
+            tracing::error!("{:#?}", e);
+        }
+        self.sx
+            .send(MsgI::ToFrontEnd(MsgFE::Profiles(profiles)))
+            .ok();
+        Ok(())
+    }
+
     pub fn update_menu_pause(&self, pause: bool) {
         MenuManipulation::update_pause(self, pause);
     }
@@ -577,6 +2142,7 @@ impl ApplicationState {
         let sqlite = self.sqlite.clone();
         let new_settings = ModelSettings::from(&frontend_state);
         ModelSettings::update(&sqlite, &new_settings).await?;
+        ModelProfile::update_active(&sqlite, &new_settings).await?;
         self.update_all_settings(&frontend_state);
         Ok(())
     }
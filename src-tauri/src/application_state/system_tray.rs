@@ -2,21 +2,52 @@ use std::sync::LazyLock;
 
 use crate::{
     SYSTEM_TRAY_ID,
-    message_handler::{MsgFE, MsgI, MsgWV},
+    db::{InterruptionType, PauseReason, Profiles},
+    message_handler::{MsgB, MsgFE, MsgI, MsgIR, MsgP, MsgWV, PauseUntil},
 };
 use tauri::{
     AppHandle, Wry,
     image::Image,
-    menu::{Menu, MenuEvent, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, Submenu},
     tray::TrayIconEvent,
 };
 use tokio::sync::broadcast::Sender;
 
+/// Number of minutes the `Postpone` menu entry will postpone a break by
+pub const TRAY_POSTPONE_MINUTES: u8 = 5;
+
+/// Number of minutes the `Extend` menu entry will add to the current work session
+pub const TRAY_EXTEND_MINUTES: u8 = 5;
+
+/// Number of minutes the `Delay` menu entry, available during the break warning, will add to the current work session
+pub const TRAY_DELAY_MINUTES: u8 = 1;
+
+/// Id, text, and duration, of each item in the `PauseFor` submenu
+const PAUSE_FOR_ENTRIES: [(&str, &str, PauseUntil); 5] = [
+    ("pause_for_15", "15 minutes", PauseUntil::Minutes(15)),
+    ("pause_for_30", "30 minutes", PauseUntil::Minutes(30)),
+    ("pause_for_60", "1 hour", PauseUntil::Minutes(60)),
+    ("pause_for_120", "2 hours", PauseUntil::Minutes(120)),
+    (
+        "pause_until_next_working_day",
+        "Until the next working day",
+        PauseUntil::NextWorkingDay,
+    ),
+];
+
+/// Prefix of the id of each item in the `Profiles` submenu, the profile_id is appended
+const PROFILE_ID_PREFIX: &str = "profile_";
+
 /// Load the Oblique Stratergies into a Lazylock vec
 #[allow(clippy::unwrap_used)]
 static ICON_PAUSE: LazyLock<Image> =
     LazyLock::new(|| Image::from_bytes(include_bytes!("../../icons/icon_paused.png")).unwrap());
 
+/// Load the end of day icon into a Lazylock
+#[allow(clippy::unwrap_used)]
+static ICON_END_OF_DAY: LazyLock<Image> =
+    LazyLock::new(|| Image::from_bytes(include_bytes!("../../icons/icon_end_of_day.png")).unwrap());
+
 /// Load the Oblique Stratergies into a Lazylock vec
 #[allow(clippy::unwrap_used)]
 static ICON_RUNNING: LazyLock<Image> =
@@ -24,37 +55,82 @@ static ICON_RUNNING: LazyLock<Image> =
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MenuEntry {
+    BreakNow,
+    Delay,
+    Extend,
+    Interruption,
+    Meeting,
     Session,
     Next,
     Pause,
+    PauseFor,
+    Postpone,
+    Profiles,
     Quit,
+    Ready,
     Settings,
+    Skip,
+    Upcoming,
 }
 
 impl MenuEntry {
     pub const fn get_id(self) -> &'static str {
         match self {
+            Self::BreakNow => "break_now",
+            Self::Delay => "delay",
+            Self::Extend => "extend",
+            Self::Interruption => "interruption",
+            Self::Meeting => "meeting",
             Self::Session => "session",
             Self::Next => "next",
             Self::Pause => "pause",
+            Self::PauseFor => "pause_for",
+            Self::Postpone => "postpone",
+            Self::Profiles => "profiles",
             Self::Quit => "quit",
+            Self::Ready => "ready",
             Self::Settings => "settings",
+            Self::Skip => "skip",
+            Self::Upcoming => "upcoming",
         }
     }
 
-    pub const fn as_str(self) -> &'static str {
+    /// The text of the entry, the number of minutes is taken from the `TRAY_*` constants, so that it always matches the behaviour
+    pub fn get_text(self) -> String {
         match self {
-            Self::Session => "session",
-            Self::Next => "loading...",
-            Self::Pause => "Pause",
-            Self::Quit => "Quit",
-            Self::Settings => "Settings",
+            Self::BreakNow => String::from("Break now"),
+            Self::Delay => format!("Postpone break {}", format_minutes(TRAY_DELAY_MINUTES)),
+            Self::Extend => format!("Add {}", format_minutes(TRAY_EXTEND_MINUTES)),
+            Self::Interruption => String::from("Log interruption"),
+            Self::Meeting => String::from("Pause for a meeting"),
+            Self::Session => String::from("session"),
+            Self::Next | Self::Upcoming => String::from("loading..."),
+            Self::Pause => String::from("Pause"),
+            Self::PauseFor => String::from("Pause for"),
+            Self::Postpone => format!("Postpone break {}", format_minutes(TRAY_POSTPONE_MINUTES)),
+            Self::Profiles => String::from("Profiles"),
+            Self::Quit => String::from("Quit"),
+            Self::Ready => String::from("Ready, start next session"),
+            Self::Settings => String::from("Settings"),
+            Self::Skip => String::from("Skip break"),
         }
     }
 }
 
+/// "1 minute", or "n minutes"
+fn format_minutes(minutes: u8) -> String {
+    if minutes == 1 {
+        String::from("1 minute")
+    } else {
+        format!("{minutes} minutes")
+    }
+}
+
 /// Change the text of the Next item, and if on break, disable all items, else enable all
+/// The break items, `Skip` & `Postpone`, are only enabled whilst on a break, `Delay` is disabled until the next break warning, and `Ready` until the break has ended
 pub fn change_menu_entry_status(system_tray_menu: &Menu<Wry>, enable: bool) {
+    change_menu_break_warning(system_tray_menu, false);
+    change_menu_awaiting_ready(system_tray_menu, false);
     if !enable {
         system_tray_menu
             .get(MenuEntry::Next.get_id())
@@ -64,18 +140,81 @@ pub fn change_menu_entry_status(system_tray_menu: &Menu<Wry>, enable: bool) {
         MenuEntry::Quit,
         MenuEntry::Settings,
         MenuEntry::Pause,
+        MenuEntry::Meeting,
         MenuEntry::Next,
         MenuEntry::Session,
+        MenuEntry::Upcoming,
+        MenuEntry::Extend,
+        MenuEntry::BreakNow,
+        MenuEntry::Interruption,
     ] {
         system_tray_menu
             .get(i.get_id())
             .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(enable).ok()));
     }
+    for i in [MenuEntry::PauseFor, MenuEntry::Profiles] {
+        system_tray_menu
+            .get(i.get_id())
+            .and_then(|i| i.as_submenu().and_then(|i| i.set_enabled(enable).ok()));
+    }
+    for i in [MenuEntry::Skip, MenuEntry::Postpone] {
+        system_tray_menu
+            .get(i.get_id())
+            .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(!enable).ok()));
+    }
+}
+
+/// Enable the `Delay` item during the break warning, and disable it otherwise
+pub fn change_menu_break_warning(system_tray_menu: &Menu<Wry>, warning: bool) {
+    system_tray_menu
+        .get(MenuEntry::Delay.get_id())
+        .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(warning).ok()));
+}
+
+/// Enable the `Ready` item, and disable `Postpone`, whilst waiting for the user to be ready after a break, and disable `Ready` otherwise
+pub fn change_menu_awaiting_ready(system_tray_menu: &Menu<Wry>, awaiting: bool) {
+    system_tray_menu
+        .get(MenuEntry::Ready.get_id())
+        .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(awaiting).ok()));
+    if awaiting {
+        system_tray_menu
+            .get(MenuEntry::Postpone.get_id())
+            .and_then(|i| i.as_menuitem().and_then(|i| i.set_enabled(false).ok()));
+    }
 }
 
-/// Change the system tray icon when paused & unpaused
-pub fn set_icon(app: &tauri::AppHandle, paused: bool) {
-    let icon = if paused {
+/// Replace the items of the `Profiles` submenu, the active profile is checked
+pub fn set_profiles(
+    app_handle: &AppHandle,
+    system_tray_menu: &Menu<Wry>,
+    profiles: &Profiles,
+) -> Result<(), tauri::Error> {
+    let Some(entry) = system_tray_menu.get(MenuEntry::Profiles.get_id()) else {
+        return Ok(());
+    };
+    let Some(submenu) = entry.as_submenu() else {
+        return Ok(());
+    };
+    while submenu.remove_at(0)?.is_some() {}
+    for profile in &profiles.profiles {
+        let item = CheckMenuItem::with_id(
+            app_handle,
+            format!("{PROFILE_ID_PREFIX}{}", profile.profile_id),
+            &profile.name,
+            true,
+            profiles.active_profile_id == Some(profile.profile_id),
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+/// Change the system tray icon when paused & unpaused, the end of day icon takes precedence over both
+pub fn set_icon(app: &tauri::AppHandle, paused: bool, end_of_day: bool) {
+    let icon = if end_of_day {
+        ICON_END_OF_DAY.clone()
+    } else if paused {
         ICON_PAUSE.clone()
     } else {
         ICON_RUNNING.clone()
@@ -84,42 +223,71 @@ pub fn set_icon(app: &tauri::AppHandle, paused: bool) {
         .and_then(|i| i.set_icon(Some(icon)).ok());
 }
 
-fn gen_menu_all_enabled(app_handle: &AppHandle) -> Result<Menu<Wry>, tauri::Error> {
-    let entry_quit = MenuItem::with_id(
-        app_handle,
-        MenuEntry::Quit.get_id(),
-        MenuEntry::Quit.as_str(),
-        true,
-        None::<&str>,
-    )?;
-    let entry_settings = MenuItem::with_id(
-        app_handle,
-        MenuEntry::Settings.get_id(),
-        MenuEntry::Settings.as_str(),
-        true,
-        None::<&str>,
-    )?;
-    let entry_pause = MenuItem::with_id(
+/// Show text next to the system tray icon, not supported on every platform, or remove it with `None`
+pub fn set_title(app: &tauri::AppHandle, title: Option<String>) {
+    if let Some(tray) = app.tray_by_id(SYSTEM_TRAY_ID) {
+        tray.set_tooltip(title.as_ref()).ok();
+        tray.set_title(title).ok();
+    }
+}
+
+/// Create the `PauseFor` submenu, with an item for each of the timed pause choices
+fn gen_pause_for_submenu(app_handle: &AppHandle) -> Result<Submenu<Wry>, tauri::Error> {
+    let submenu = Submenu::with_id(
         app_handle,
-        MenuEntry::Pause.get_id(),
-        MenuEntry::Pause.as_str(),
+        MenuEntry::PauseFor.get_id(),
+        MenuEntry::PauseFor.get_text(),
         true,
-        None::<&str>,
     )?;
-    let entry_next = MenuItem::with_id(
+    for (id, text, _) in PAUSE_FOR_ENTRIES {
+        submenu.append(&MenuItem::with_id(
+            app_handle,
+            id,
+            text,
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+/// Create a single menu item, using the id & text of the given entry
+fn gen_menu_item(
+    app_handle: &AppHandle,
+    entry: MenuEntry,
+    enabled: bool,
+) -> Result<MenuItem<Wry>, tauri::Error> {
+    MenuItem::with_id(
         app_handle,
-        MenuEntry::Next.get_id(),
-        MenuEntry::Next.as_str(),
-        true,
+        entry.get_id(),
+        entry.get_text(),
+        enabled,
         None::<&str>,
-    )?;
-    let entry_session = MenuItem::with_id(
+    )
+}
+
+fn gen_menu_all_enabled(app_handle: &AppHandle) -> Result<Menu<Wry>, tauri::Error> {
+    let entry_quit = gen_menu_item(app_handle, MenuEntry::Quit, true)?;
+    let entry_settings = gen_menu_item(app_handle, MenuEntry::Settings, true)?;
+    let entry_pause = gen_menu_item(app_handle, MenuEntry::Pause, true)?;
+    let entry_meeting = gen_menu_item(app_handle, MenuEntry::Meeting, true)?;
+    let entry_pause_for = gen_pause_for_submenu(app_handle)?;
+    let entry_next = gen_menu_item(app_handle, MenuEntry::Next, true)?;
+    let entry_session = gen_menu_item(app_handle, MenuEntry::Session, true)?;
+    let entry_upcoming = gen_menu_item(app_handle, MenuEntry::Upcoming, true)?;
+    let entry_extend = gen_menu_item(app_handle, MenuEntry::Extend, true)?;
+    let entry_break_now = gen_menu_item(app_handle, MenuEntry::BreakNow, true)?;
+    let entry_delay = gen_menu_item(app_handle, MenuEntry::Delay, false)?;
+    let entry_interruption = gen_menu_item(app_handle, MenuEntry::Interruption, true)?;
+    let entry_profiles = Submenu::with_id(
         app_handle,
-        MenuEntry::Session.get_id(),
-        MenuEntry::Session.as_str(),
+        MenuEntry::Profiles.get_id(),
+        MenuEntry::Profiles.get_text(),
         true,
-        None::<&str>,
     )?;
+    let entry_skip = gen_menu_item(app_handle, MenuEntry::Skip, false)?;
+    let entry_postpone = gen_menu_item(app_handle, MenuEntry::Postpone, false)?;
+    let entry_ready = gen_menu_item(app_handle, MenuEntry::Ready, false)?;
 
     Menu::with_items(
         app_handle,
@@ -127,8 +295,19 @@ fn gen_menu_all_enabled(app_handle: &AppHandle) -> Result<Menu<Wry>, tauri::Erro
             &entry_quit,
             &entry_settings,
             &entry_pause,
+            &entry_meeting,
+            &entry_pause_for,
             &entry_next,
             &entry_session,
+            &entry_upcoming,
+            &entry_extend,
+            &entry_break_now,
+            &entry_delay,
+            &entry_interruption,
+            &entry_profiles,
+            &entry_skip,
+            &entry_postpone,
+            &entry_ready,
         ],
     )
 }
@@ -158,6 +337,13 @@ fn on_tray_event(event: TrayIconEvent, sx: Sender<MsgI>) {
 
 /// Handle interaction events on the systemtray icon/menu
 fn on_menu_entry_event(event: &MenuEvent, sx: &Sender<MsgI>) {
+    if let Some((_, _, until)) = PAUSE_FOR_ENTRIES
+        .iter()
+        .find(|(id, ..)| *id == event.id.as_ref())
+    {
+        sx.send(MsgI::PauseUntil(*until)).ok();
+        return;
+    }
     match event.id.as_ref() {
         val if val == MenuEntry::Settings.get_id() => {
             sx.send(MsgI::ToFrontEnd(MsgFE::GoToSettings)).ok();
@@ -165,8 +351,45 @@ fn on_menu_entry_event(event: &MenuEvent, sx: &Sender<MsgI>) {
         val if val == MenuEntry::Quit.get_id() => {
             sx.send(MsgI::Window(MsgWV::Close)).ok();
         }
+        val if val == MenuEntry::BreakNow.get_id() => {
+            sx.send(MsgI::Break(MsgB::Start)).ok();
+        }
+        val if val == MenuEntry::Delay.get_id() => {
+            sx.send(MsgI::Extend(TRAY_DELAY_MINUTES)).ok();
+        }
+        val if val == MenuEntry::Extend.get_id() => {
+            sx.send(MsgI::Extend(TRAY_EXTEND_MINUTES)).ok();
+        }
+        val if val == MenuEntry::Interruption.get_id() => {
+            sx.send(MsgI::Interruption(MsgIR::Record(
+                InterruptionType::External,
+                None,
+            )))
+            .ok();
+        }
         val if val == MenuEntry::Pause.get_id() => {
-            sx.send(MsgI::Pause).ok();
+            sx.send(MsgI::Pause(PauseReason::Manual)).ok();
+        }
+        val if val == MenuEntry::Meeting.get_id() => {
+            sx.send(MsgI::SetPaused(true, PauseReason::Meeting)).ok();
+        }
+        val if val == MenuEntry::Postpone.get_id() => {
+            sx.send(MsgI::Break(MsgB::Postpone(TRAY_POSTPONE_MINUTES)))
+                .ok();
+        }
+        val if val == MenuEntry::Ready.get_id() => {
+            sx.send(MsgI::Break(MsgB::Ready)).ok();
+        }
+        val if val == MenuEntry::Skip.get_id() => {
+            sx.send(MsgI::Break(MsgB::Skip)).ok();
+        }
+        val if val.starts_with(PROFILE_ID_PREFIX) => {
+            if let Some(profile_id) = val
+                .strip_prefix(PROFILE_ID_PREFIX)
+                .and_then(|i| i.parse::<i64>().ok())
+            {
+                sx.send(MsgI::Profile(MsgP::Activate(profile_id))).ok();
+            }
         }
         _ => (),
     }
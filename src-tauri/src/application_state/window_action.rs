@@ -1,8 +1,20 @@
-use tauri::WebviewWindow;
 use tauri::{AppHandle, Manager};
+use tauri::{LogicalSize, WebviewWindow};
 
 use crate::MAIN_WINDOW;
 
+/// The standard size of the main window, matches tauri.conf.json
+const WINDOW_SIZE: LogicalSize<u32> = LogicalSize {
+    width: 1000,
+    height: 850,
+};
+
+/// The size of the main window whilst showing the break warning
+const WARNING_SIZE: LogicalSize<u32> = LogicalSize {
+    width: 400,
+    height: 200,
+};
+
 pub struct WindowAction;
 
 impl WindowAction {
@@ -52,6 +64,12 @@ impl WindowAction {
         }
     }
 
+    /// Resize, then show, the window
+    fn show_sized(window: &WebviewWindow, fullscreen: bool, size: LogicalSize<u32>) {
+        window.set_size(size).ok();
+        Self::show(window, fullscreen);
+    }
+
     /// Change from full screen to the standard window size
     fn _remove_fullscreen(window: &WebviewWindow) {
         window.set_fullscreen(false).ok();
@@ -68,7 +86,14 @@ impl WindowAction {
     /// show window
     pub fn show_window(app: &AppHandle, fullscreen: bool) {
         if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
-            Self::show(&window, fullscreen);
+            Self::show_sized(&window, fullscreen, WINDOW_SIZE);
+        }
+    }
+
+    /// Show the window, at a small size, for the break warning
+    pub fn show_warning_window(app: &AppHandle) {
+        if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+            Self::show_sized(&window, false, WARNING_SIZE);
         }
     }
 
@@ -84,7 +109,7 @@ impl WindowAction {
         if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
             match window.is_visible() {
                 Ok(true) => Self::hide(&window, fullscreen),
-                Ok(false) => Self::show(&window, fullscreen),
+                Ok(false) => Self::show_sized(&window, fullscreen, WINDOW_SIZE),
                 Err(_) => app.exit(1),
             }
         }
@@ -9,7 +9,7 @@ use crate::{
 // Get a reqwest client, use application name and version an UserAgent, this should never err
 fn get_client() -> Result<reqwest::Client, AppError> {
     reqwest::Client::builder()
-        .connect_timeout(std::time::Duration::from_millis(5000))
+        .connect_timeout(std::time::Duration::from_secs(5))
         .gzip(true)
         .brotli(true)
         .user_agent(format!(
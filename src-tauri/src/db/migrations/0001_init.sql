@@ -40,4 +40,4 @@ CREATE TABLE IF NOT EXISTS stats (
 -- 	long_break_length INTEGER NOT NULL DEFAULT 15,
 -- 	session_length INTEGER NOT NULL DEFAULT 25,
 -- 	number_session INTEGER NOT NULL DEFAULT 4,
--- ) STRICT;
\ No newline at end of file
+-- ) STRICT;
@@ -1,5 +1,3 @@
-BEGIN;
-
 -- Remove checks from settings & add auto_pause column
 ALTER TABLE
     settings RENAME TO settings_old;
@@ -135,5 +133,3 @@ WHERE (SELECT has_auto_pause_timespan_sec FROM temp_column_check) = 0;
 
 DROP TABLE temp_column_check;
 DROP TABLE settings_old;
-
-COMMIT;
\ No newline at end of file
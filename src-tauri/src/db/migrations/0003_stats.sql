@@ -0,0 +1,14 @@
+-- The original stats table was never written to, so can be dropped & re-created
+DROP TABLE IF EXISTS stats;
+
+CREATE TABLE stats (
+	stats_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	session_type TEXT NOT NULL,
+	started_at INTEGER NOT NULL,
+	ended_at INTEGER NOT NULL,
+	planned_sec INTEGER NOT NULL,
+	actual_sec INTEGER NOT NULL,
+	outcome TEXT NOT NULL
+);
+
+CREATE INDEX stats_started_at_index ON stats(started_at);
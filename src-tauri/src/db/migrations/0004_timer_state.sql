@@ -0,0 +1,15 @@
+-- Single row snapshot of the timer, so it can be restored after a restart
+CREATE TABLE timer_state (
+	timer_state_id INTEGER PRIMARY KEY AUTOINCREMENT CHECK (timer_state_id = 1),
+	session_type TEXT NOT NULL,
+	planned_sec INTEGER NOT NULL,
+	remaining_sec INTEGER NOT NULL,
+	session_count INTEGER NOT NULL,
+	paused BOOLEAN NOT NULL,
+	pause_after_break BOOLEAN NOT NULL,
+	session_started_at INTEGER NOT NULL,
+	updated_at INTEGER NOT NULL
+);
+
+-- Maximum age of a timer_state snapshot that will be restored on start, 0 disables restoring
+ALTER TABLE settings ADD COLUMN restore_max_age_sec INTEGER NOT NULL DEFAULT 3600;
@@ -0,0 +1,13 @@
+-- Events that aren't a session, such as the system being suspended
+CREATE TABLE event (
+	event_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	event_type TEXT NOT NULL,
+	started_at INTEGER NOT NULL,
+	duration_sec INTEGER NOT NULL,
+	detail TEXT
+);
+
+CREATE INDEX event_started_at_index ON event(started_at);
+
+-- What to do with the timer after the system has been suspended
+ALTER TABLE settings ADD COLUMN suspend_policy TEXT NOT NULL DEFAULT 'resume';
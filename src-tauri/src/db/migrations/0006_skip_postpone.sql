@@ -0,0 +1,5 @@
+-- Number of times a break can be postponed each day
+ALTER TABLE settings ADD COLUMN postpone_limit_per_day INTEGER NOT NULL DEFAULT 3;
+
+-- Add any time left of a skipped break onto the next break
+ALTER TABLE settings ADD COLUMN carry_over_skipped BOOLEAN NOT NULL DEFAULT FALSE;
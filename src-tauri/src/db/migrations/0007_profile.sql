@@ -0,0 +1,39 @@
+-- Named sets of session, break, and auto-pause settings, built in profiles can't be renamed, deleted, or edited
+CREATE TABLE profile (
+	profile_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	name TEXT NOT NULL UNIQUE,
+	built_in BOOLEAN NOT NULL DEFAULT FALSE,
+	auto_pause BOOLEAN NOT NULL,
+	auto_pause_threshold INTEGER NOT NULL,
+	auto_pause_timespan_sec INTEGER NOT NULL,
+	auto_resume BOOLEAN NOT NULL,
+	auto_resume_threshold INTEGER NOT NULL,
+	auto_resume_timespan_sec INTEGER NOT NULL,
+	long_break_as_sec INTEGER NOT NULL,
+	number_session_before_break INTEGER NOT NULL,
+	session_as_sec INTEGER NOT NULL,
+	short_break_as_sec INTEGER NOT NULL
+);
+
+INSERT INTO
+	profile (
+		name,
+		built_in,
+		auto_pause,
+		auto_pause_threshold,
+		auto_pause_timespan_sec,
+		auto_resume,
+		auto_resume_threshold,
+		auto_resume_timespan_sec,
+		long_break_as_sec,
+		number_session_before_break,
+		session_as_sec,
+		short_break_as_sec
+	)
+VALUES
+	('Classic 25/5', TRUE, FALSE, 5, 300, FALSE, 5, 300, 900, 4, 1500, 300),
+	('52/17', TRUE, FALSE, 5, 300, FALSE, 5, 300, 1800, 4, 3120, 1020),
+	('Ultradian 90/20', TRUE, FALSE, 5, 300, FALSE, 5, 300, 1800, 2, 5400, 1200);
+
+-- The profile that the settings row is currently a copy of
+ALTER TABLE settings ADD COLUMN profile_id INTEGER REFERENCES profile(profile_id) ON DELETE SET NULL;
@@ -0,0 +1,13 @@
+-- Ordered work & break segments of a profile, a profile without any segments uses the number_session_before_break pattern
+CREATE TABLE cycle_segment (
+	cycle_segment_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	profile_id INTEGER NOT NULL REFERENCES profile(profile_id) ON DELETE CASCADE,
+	position INTEGER NOT NULL,
+	segment_type TEXT NOT NULL,
+	duration_sec INTEGER NOT NULL,
+	label TEXT,
+	UNIQUE (profile_id, position)
+);
+
+-- Position, in the cycle of the active profile, of the current segment
+ALTER TABLE timer_state ADD COLUMN cycle_index INTEGER NOT NULL DEFAULT 0;
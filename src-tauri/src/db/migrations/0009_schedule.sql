@@ -0,0 +1,17 @@
+-- Working hours, the timer is paused outside of these windows, weekday 0 is Sunday, minutes are since the start of the day, in localtime
+CREATE TABLE schedule_window (
+	schedule_window_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	weekday INTEGER NOT NULL,
+	start_minute INTEGER NOT NULL,
+	end_minute INTEGER NOT NULL
+);
+
+-- Dates, such as holidays, which are entirely outside of working hours
+CREATE TABLE schedule_exception (
+	schedule_exception_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	date TEXT NOT NULL UNIQUE,
+	label TEXT
+);
+
+-- Why the timer was paused, NULL if not paused
+ALTER TABLE timer_state ADD COLUMN pause_reason TEXT;
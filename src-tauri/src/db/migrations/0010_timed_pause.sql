@@ -0,0 +1,2 @@
+-- Unix timestamp when a timed pause will automatically resume, NULL if not paused, or paused indefinitely
+ALTER TABLE timer_state ADD COLUMN resume_at INTEGER;
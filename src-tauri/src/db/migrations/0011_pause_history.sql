@@ -0,0 +1,25 @@
+-- Every pause of the timer, and every suspension of the system, inserted once it has ended
+CREATE TABLE pause_history (
+	pause_history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	pause_reason TEXT NOT NULL,
+	started_at INTEGER NOT NULL,
+	duration_sec INTEGER NOT NULL,
+	resume_behaviour TEXT
+);
+
+CREATE INDEX pause_history_started_at_index ON pause_history(started_at);
+
+-- What happens to the current session when the timer is resumed, for each pause reason
+CREATE TABLE resume_behaviour (
+	pause_reason TEXT PRIMARY KEY NOT NULL,
+	resume_behaviour TEXT NOT NULL
+);
+
+INSERT INTO
+	resume_behaviour (pause_reason, resume_behaviour)
+VALUES
+	('manual', 'continue'),
+	('cpu', 'continue'),
+	('schedule', 'restart'),
+	('meeting', 'restart'),
+	('after_break', 'continue');
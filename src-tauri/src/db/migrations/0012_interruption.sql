@@ -0,0 +1,14 @@
+-- Interruptions logged during a work session, the session is identified by the time it started
+CREATE TABLE interruption (
+	interruption_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	session_started_at INTEGER NOT NULL,
+	interruption_type TEXT NOT NULL,
+	note TEXT,
+	created_at INTEGER NOT NULL
+);
+
+CREATE INDEX interruption_session_started_at_index ON interruption(session_started_at);
+
+ALTER TABLE stats ADD COLUMN internal_interruptions INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE stats ADD COLUMN external_interruptions INTEGER NOT NULL DEFAULT 0;
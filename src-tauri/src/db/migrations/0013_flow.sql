@@ -0,0 +1,13 @@
+-- Single row of flow mode options, whilst enabled work sessions count up until ended, and the break length is a percentage of the work done
+CREATE TABLE flow (
+	flow_id INTEGER PRIMARY KEY AUTOINCREMENT CHECK (flow_id = 1),
+	enabled BOOLEAN NOT NULL,
+	break_percent INTEGER NOT NULL,
+	min_break_sec INTEGER NOT NULL,
+	max_break_sec INTEGER NOT NULL
+);
+
+INSERT INTO
+	flow (flow_id, enabled, break_percent, min_break_sec, max_break_sec)
+VALUES
+	(1, FALSE, 20, 300, 1800);
@@ -0,0 +1,12 @@
+-- Single row of clock-aligned session options, whilst enabled each period, counted from local midnight, is work followed by a break at its end
+CREATE TABLE alignment (
+	alignment_id INTEGER PRIMARY KEY AUTOINCREMENT CHECK (alignment_id = 1),
+	enabled BOOLEAN NOT NULL,
+	period_minutes INTEGER NOT NULL,
+	break_minutes INTEGER NOT NULL
+);
+
+INSERT INTO
+	alignment (alignment_id, enabled, period_minutes, break_minutes)
+VALUES
+	(1, FALSE, 30, 5);
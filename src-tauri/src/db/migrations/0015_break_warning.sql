@@ -0,0 +1,2 @@
+-- Number of seconds before a break that a warning is shown, 0 disables the warning
+ALTER TABLE settings ADD COLUMN break_warning_sec INTEGER NOT NULL DEFAULT 30;
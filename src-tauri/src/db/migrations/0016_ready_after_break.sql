@@ -0,0 +1,2 @@
+-- Once a break has ended, wait for the user to be ready before starting the next work session
+ALTER TABLE settings ADD COLUMN ready_after_break BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+-- Seconds of continuous active use before a break is forced, and seconds of active use in a day before the end of day, 0 disables either
+ALTER TABLE settings ADD COLUMN overwork_limit_sec INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN daily_cap_sec INTEGER NOT NULL DEFAULT 0;
+
+-- Seconds of active computer use for each local day, day is YYYY-MM-DD
+CREATE TABLE active_day (
+	day TEXT PRIMARY KEY NOT NULL,
+	active_sec INTEGER NOT NULL
+);
+
+INSERT INTO
+	resume_behaviour (pause_reason, resume_behaviour)
+VALUES
+	('end_of_day', 'restart');
@@ -0,0 +1,19 @@
+-- User-defined recurring reminders, minutes are since the start of the day, in localtime, end_minute is exclusive
+CREATE TABLE reminder (
+	reminder_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	label TEXT NOT NULL,
+	interval_sec INTEGER NOT NULL,
+	duration_sec INTEGER NOT NULL,
+	display_mode TEXT NOT NULL,
+	start_minute INTEGER NOT NULL,
+	end_minute INTEGER NOT NULL,
+	pause_with_timer BOOLEAN NOT NULL,
+	enabled BOOLEAN NOT NULL
+);
+
+INSERT INTO
+	reminder (label, interval_sec, duration_sec, display_mode, start_minute, end_minute, pause_with_timer, enabled)
+VALUES
+	('Look 20 feet away for 20 seconds', 1200, 20, 'overlay', 0, 1440, TRUE, FALSE),
+	('Drink some water', 3600, 10, 'notification', 0, 1440, FALSE, FALSE),
+	('Check your posture', 1800, 10, 'notification', 0, 1440, TRUE, FALSE);
@@ -0,0 +1,14 @@
+-- Single row of activity signal options, source is one of cpu, network, disk, any, or all, thresholds are in KB/s, a threshold of 0 excludes the signal from any & all
+CREATE TABLE activity (
+	activity_id INTEGER PRIMARY KEY AUTOINCREMENT CHECK (activity_id = 1),
+	source TEXT NOT NULL,
+	network_threshold_kb INTEGER NOT NULL,
+	network_timespan_sec INTEGER NOT NULL,
+	disk_threshold_kb INTEGER NOT NULL,
+	disk_timespan_sec INTEGER NOT NULL
+);
+
+INSERT INTO
+	activity (activity_id, source, network_threshold_kb, network_timespan_sec, disk_threshold_kb, disk_timespan_sec)
+VALUES
+	(1, 'cpu', 0, 300, 0, 300);
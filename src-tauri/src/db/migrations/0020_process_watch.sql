@@ -0,0 +1,12 @@
+-- Processes that pause the timer whilst running, a pattern is either a process name, matched case-insensitively, or a regex
+CREATE TABLE process_watch (
+	process_watch_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	pattern TEXT NOT NULL,
+	is_regex BOOLEAN NOT NULL,
+	enabled BOOLEAN NOT NULL
+);
+
+INSERT INTO
+	resume_behaviour (pause_reason, resume_behaviour)
+VALUES
+	('process', 'restart');
@@ -0,0 +1,8 @@
+-- How the cpu signal is measured, one of global, max_core, or processes
+ALTER TABLE activity ADD COLUMN cpu_mode TEXT NOT NULL DEFAULT 'global';
+
+-- Process names whose summed cpu usage is measured when cpu_mode is processes
+CREATE TABLE cpu_process (
+	cpu_process_id INTEGER PRIMARY KEY AUTOINCREMENT,
+	name TEXT NOT NULL
+);
@@ -0,0 +1,10 @@
+-- How samples are reduced to a single value, one of mean, median, percentile, or ewma, and the options to stop auto-pause & auto-resume flapping
+ALTER TABLE activity ADD COLUMN aggregation TEXT NOT NULL DEFAULT 'mean';
+
+ALTER TABLE activity ADD COLUMN percentile INTEGER NOT NULL DEFAULT 90;
+
+ALTER TABLE activity ADD COLUMN ewma_alpha_percent INTEGER NOT NULL DEFAULT 10;
+
+ALTER TABLE activity ADD COLUMN hysteresis_band INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE activity ADD COLUMN dwell_sec INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,11 @@
+-- How many days per-minute activity history is kept for
+ALTER TABLE activity ADD COLUMN history_retention_days INTEGER NOT NULL DEFAULT 30;
+
+-- Per-minute averages of every activity signal, minute is the unix time at the start of the minute, a signal without samples is NULL
+CREATE TABLE activity_history (
+	minute INTEGER PRIMARY KEY,
+	cpu REAL,
+	cpu_mode TEXT NOT NULL,
+	network_kb REAL,
+	disk_kb REAL
+);
@@ -3,7 +3,26 @@ use std::path::{Path, PathBuf};
 
 mod models;
 
-pub use models::settings::ModelSettings;
+pub use models::{
+    active_day::ModelActiveDay,
+    activity::{CpuMode, ModelActivity, SignalSource},
+    activity_history::{ActivityHistory, ModelActivityHistory},
+    alignment::ModelAlignment,
+    cycle::ModelCycleSegment,
+    event::{EventType, ModelEvent},
+    flow::ModelFlow,
+    interruption::{InterruptionType, ModelInterruption},
+    pause::{ModelPause, ModelResumeBehaviour, PauseReason, ResumeBehaviour},
+    process_watch::ModelProcessWatch,
+    profile::{ModelProfile, Profiles},
+    reminder::{ModelReminder, ReminderDisplay},
+    schedule::ModelSchedule,
+    settings::{ModelSettings, SuspendPolicy},
+    stats::{
+        ModelStats, SessionOutcome, SessionType, StatsHeatmap, StatsPeriod, StatsTotals, now_as_sec,
+    },
+    timer_state::ModelTimerState,
+};
 
 use crate::app_error::AppError;
 /// Open Sqlite pool connection, and return
@@ -21,21 +40,53 @@ async fn get_db(path: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
     Ok(db)
 }
 
-async fn run_migrations(db: &SqlitePool) {
-    let migrations = include_str!("migrations.sql");
-    if let Err(e) = sqlx::query(migrations).execute(db).await {
-        println!("{e:?}");
-        std::process::exit(1);
-    }
-}
+/// Numbered migrations, executed in order, each one exactly once, the SQLite `user_version` pragma stores the number of migrations applied
+const MIGRATIONS: [&str; 23] = [
+    include_str!("migrations/0001_init.sql"),
+    include_str!("migrations/0002_settings_auto_pause.sql"),
+    include_str!("migrations/0003_stats.sql"),
+    include_str!("migrations/0004_timer_state.sql"),
+    include_str!("migrations/0005_event.sql"),
+    include_str!("migrations/0006_skip_postpone.sql"),
+    include_str!("migrations/0007_profile.sql"),
+    include_str!("migrations/0008_cycle.sql"),
+    include_str!("migrations/0009_schedule.sql"),
+    include_str!("migrations/0010_timed_pause.sql"),
+    include_str!("migrations/0011_pause_history.sql"),
+    include_str!("migrations/0012_interruption.sql"),
+    include_str!("migrations/0013_flow.sql"),
+    include_str!("migrations/0014_alignment.sql"),
+    include_str!("migrations/0015_break_warning.sql"),
+    include_str!("migrations/0016_ready_after_break.sql"),
+    include_str!("migrations/0017_overwork.sql"),
+    include_str!("migrations/0018_reminder.sql"),
+    include_str!("migrations/0019_activity.sql"),
+    include_str!("migrations/0020_process_watch.sql"),
+    include_str!("migrations/0021_cpu_mode.sql"),
+    include_str!("migrations/0022_aggregation.sql"),
+    include_str!("migrations/0023_activity_history.sql"),
+];
 
-/// Create, if they don't exists, all the sql tables
-async fn create_tables(db: &SqlitePool) {
-    let init_db = include_str!("init_db.sql");
-    if let Err(e) = sqlx::query(init_db).execute(db).await {
-        println!("{e:?}");
-        std::process::exit(1);
+/// Run, each inside its own transaction, any migrations that haven't yet been applied
+async fn run_migrations(db: &SqlitePool) -> Result<(), AppError> {
+    let user_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+        .fetch_one(db)
+        .await?;
+    let applied = usize::try_from(user_version).unwrap_or_default();
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let version = index + 1;
+        let mut transaction = db.begin().await?;
+        sqlx::query(migration)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| AppError::Migration(version, e))?;
+        sqlx::query(&format!("PRAGMA user_version = {version}"))
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| AppError::Migration(version, e))?;
+        transaction.commit().await?;
     }
+    Ok(())
 }
 
 /// Init db connection, works if folder/files exists or not
@@ -45,7 +96,6 @@ pub async fn init_db(data_location: &Path) -> Result<SqlitePool, AppError> {
         data_location.display()
     )))
     .await?;
-    create_tables(&db).await;
-    run_migrations(&db).await;
+    run_migrations(&db).await?;
     Ok(db)
 }
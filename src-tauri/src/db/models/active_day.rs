@@ -0,0 +1,29 @@
+use sqlx::SqlitePool;
+
+use crate::app_error::AppError;
+
+/// Seconds of active computer use in a single local day, tracked regardless of the timer
+pub struct ModelActiveDay;
+
+impl ModelActiveDay {
+    /// Get the active seconds of the given day, day is YYYY-MM-DD
+    pub async fn get(sqlite: &SqlitePool, day: &str) -> Result<u32, AppError> {
+        let query = "SELECT active_sec FROM active_day WHERE day = $1";
+        Ok(sqlx::query_scalar(query)
+            .bind(day)
+            .fetch_optional(sqlite)
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Insert, or replace, the active seconds of the given day
+    pub async fn upsert(sqlite: &SqlitePool, day: &str, active_sec: u32) -> Result<(), AppError> {
+        let query = "INSERT OR REPLACE INTO active_day(day, active_sec) VALUES($1, $2)";
+        sqlx::query(query)
+            .bind(day)
+            .bind(active_sec)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{activity::Aggregator, app_error::AppError};
+
+/// The longest timespan that a signal can be averaged over, matches the length of the stored usage history
+const MAX_TIMESPAN_SEC: u16 = 60 * 15;
+
+/// The longest minimum time between two auto transitions
+const MAX_DWELL_SEC: u16 = 60 * 60;
+
+/// The longest that per-minute activity history is kept for
+const MAX_RETENTION_DAYS: u16 = 365;
+
+/// Which activity signal is used to auto-pause & auto-resume
+#[derive(sqlx::Type, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SignalSource {
+    /// Global cpu usage, using the auto-pause & auto-resume thresholds and timespans
+    #[default]
+    Cpu,
+    Network,
+    Disk,
+    /// Active if any signal is active, idle once every signal is idle
+    Any,
+    /// Active once every signal is active, idle if any signal is idle
+    All,
+}
+
+/// How the cpu signal is measured
+#[derive(sqlx::Type, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CpuMode {
+    /// Usage of all cores combined
+    #[default]
+    Global,
+    /// Usage of the busiest single core
+    MaxCore,
+    /// Summed usage of every process in `cpu_processes`, each process as a percentage of a single core
+    Processes,
+}
+
+/// How the samples over a signal's timespan are reduced to a single value
+#[derive(sqlx::Type, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    #[default]
+    Mean,
+    Median,
+    /// Uses `percentile`
+    Percentile,
+    /// Uses `ewma_alpha_percent`
+    Ewma,
+}
+
+/// Activity signal options, network & disk thresholds are in KB/s, a threshold of 0 excludes the signal from `Any` & `All`
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelActivity {
+    pub source: SignalSource,
+    pub cpu_mode: CpuMode,
+    /// Process names, matched ignoring case, and any `.exe` extension, stored in the `cpu_process` table
+    #[sqlx(skip)]
+    pub cpu_processes: Vec<String>,
+    pub network_threshold_kb: u32,
+    pub network_timespan_sec: u16,
+    pub disk_threshold_kb: u32,
+    pub disk_timespan_sec: u16,
+    pub aggregation: Aggregation,
+    pub percentile: u8,
+    /// The weight, as a percentage, of each newer sample
+    pub ewma_alpha_percent: u8,
+    /// After an auto transition, reversing it needs the cpu usage to pass its threshold by this many percent
+    pub hysteresis_band: u8,
+    /// The minimum time between two auto transitions
+    pub dwell_sec: u16,
+    /// How long per-minute activity history is kept for
+    pub history_retention_days: u16,
+}
+
+impl Default for ModelActivity {
+    fn default() -> Self {
+        Self {
+            source: SignalSource::Cpu,
+            cpu_mode: CpuMode::Global,
+            cpu_processes: vec![],
+            network_threshold_kb: 0,
+            network_timespan_sec: 300,
+            disk_threshold_kb: 0,
+            disk_timespan_sec: 300,
+            aggregation: Aggregation::Mean,
+            percentile: 90,
+            ewma_alpha_percent: 10,
+            hysteresis_band: 0,
+            dwell_sec: 0,
+            history_retention_days: 30,
+        }
+    }
+}
+
+impl ModelActivity {
+    pub fn get_aggregator(&self) -> Aggregator {
+        match self.aggregation {
+            Aggregation::Mean => Aggregator::Mean,
+            Aggregation::Median => Aggregator::Median,
+            Aggregation::Percentile => Aggregator::Percentile(self.percentile),
+            Aggregation::Ewma => Aggregator::Ewma(f32::from(self.ewma_alpha_percent) / 100.0),
+        }
+    }
+
+    /// Timespans must be between 1 second and 15 minutes, a network or disk source needs a threshold, and measuring processes needs at least one process name
+    /// The percentile & ewma alpha must be between 1 and 100, the hysteresis band at most 100, and the dwell time at most an hour
+    /// History must be kept for between 1 day and a year
+    pub fn validate(&self) -> Result<(), AppError> {
+        let timespans = [self.network_timespan_sec, self.disk_timespan_sec];
+        if timespans
+            .iter()
+            .any(|i| !(1..=MAX_TIMESPAN_SEC).contains(i))
+            || (self.source == SignalSource::Network && self.network_threshold_kb == 0)
+            || (self.source == SignalSource::Disk && self.disk_threshold_kb == 0)
+            || (self.cpu_mode == CpuMode::Processes && self.cpu_processes.is_empty())
+            || self.cpu_processes.iter().any(|i| i.trim().is_empty())
+            || !(1..=100).contains(&self.percentile)
+            || !(1..=100).contains(&self.ewma_alpha_percent)
+            || self.hysteresis_band > 100
+            || self.dwell_sec > MAX_DWELL_SEC
+            || !(1..=MAX_RETENTION_DAYS).contains(&self.history_retention_days)
+        {
+            return Err(AppError::Internal(format!(
+                "Invalid activity options {self:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get(sqlite: &SqlitePool) -> Result<Self, AppError> {
+        let query = "
+SELECT
+    source,
+    cpu_mode,
+    network_threshold_kb,
+    network_timespan_sec,
+    disk_threshold_kb,
+    disk_timespan_sec,
+    aggregation,
+    percentile,
+    ewma_alpha_percent,
+    hysteresis_band,
+    dwell_sec,
+    history_retention_days
+FROM
+    activity";
+        let mut activity = sqlx::query_as::<_, Self>(query)
+            .fetch_optional(sqlite)
+            .await?
+            .unwrap_or_default();
+        let query = "
+SELECT
+    name
+FROM
+    cpu_process
+ORDER BY
+    cpu_process_id";
+        activity.cpu_processes = sqlx::query_scalar::<_, String>(query)
+            .fetch_all(sqlite)
+            .await?;
+        Ok(activity)
+    }
+
+    /// Update the options, and replace all cpu process names
+    pub async fn update(sqlite: &SqlitePool, activity: &Self) -> Result<(), AppError> {
+        activity.validate()?;
+        let mut transaction = sqlite.begin().await?;
+        let query = "
+UPDATE
+    activity
+SET
+    source = $1,
+    cpu_mode = $2,
+    network_threshold_kb = $3,
+    network_timespan_sec = $4,
+    disk_threshold_kb = $5,
+    disk_timespan_sec = $6,
+    aggregation = $7,
+    percentile = $8,
+    ewma_alpha_percent = $9,
+    hysteresis_band = $10,
+    dwell_sec = $11,
+    history_retention_days = $12";
+        sqlx::query(query)
+            .bind(activity.source)
+            .bind(activity.cpu_mode)
+            .bind(activity.network_threshold_kb)
+            .bind(activity.network_timespan_sec)
+            .bind(activity.disk_threshold_kb)
+            .bind(activity.disk_timespan_sec)
+            .bind(activity.aggregation)
+            .bind(activity.percentile)
+            .bind(activity.ewma_alpha_percent)
+            .bind(activity.hysteresis_band)
+            .bind(activity.dwell_sec)
+            .bind(activity.history_retention_days)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM cpu_process")
+            .execute(&mut *transaction)
+            .await?;
+        let query = "
+INSERT INTO
+    cpu_process(name)
+VALUES($1)";
+        for name in &activity.cpu_processes {
+            sqlx::query(query)
+                .bind(name.trim())
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+}
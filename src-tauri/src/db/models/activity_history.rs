@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{
+    app_error::AppError,
+    db::{CpuMode, ModelPause, ModelStats},
+};
+
+const ONE_DAY_AS_SEC: i64 = 60 * 60 * 24;
+
+/// The average of every activity signal over a single minute, `minute` is the unix time at the start of the minute
+/// A signal is `None` if it had no samples during the minute
+#[derive(FromRow, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelActivityHistory {
+    pub minute: i64,
+    pub cpu: Option<f32>,
+    pub cpu_mode: CpuMode,
+    pub network_kb: Option<f32>,
+    pub disk_kb: Option<f32>,
+}
+
+/// Activity history, along with the pauses & sessions, that overlap a time range
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityHistory {
+    pub from: i64,
+    pub to: i64,
+    pub minutes: Vec<ModelActivityHistory>,
+    pub pauses: Vec<ModelPause>,
+    pub sessions: Vec<ModelStats>,
+}
+
+impl ModelActivityHistory {
+    /// Insert, or replace, a single minute, and remove every minute older than `retention_days`
+    pub async fn insert(
+        sqlite: &SqlitePool,
+        history: &Self,
+        retention_days: u16,
+    ) -> Result<(), AppError> {
+        let mut transaction = sqlite.begin().await?;
+        let query = "
+INSERT OR REPLACE INTO
+    activity_history(
+    minute,
+    cpu,
+    cpu_mode,
+    network_kb,
+    disk_kb
+    )
+VALUES($1, $2, $3, $4, $5)";
+        sqlx::query(query)
+            .bind(history.minute)
+            .bind(history.cpu)
+            .bind(history.cpu_mode)
+            .bind(history.network_kb)
+            .bind(history.disk_kb)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM activity_history WHERE minute < $1")
+            .bind(history.minute - i64::from(retention_days) * ONE_DAY_AS_SEC)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Get every minute, pause, and session that overlaps the range, `from` & `to` are unix times
+    pub async fn get_range(
+        sqlite: &SqlitePool,
+        from: i64,
+        to: i64,
+    ) -> Result<ActivityHistory, AppError> {
+        if from > to {
+            return Err(AppError::Internal(format!(
+                "Invalid activity history range {from} - {to}"
+            )));
+        }
+        let query = "
+SELECT
+    minute,
+    cpu,
+    cpu_mode,
+    network_kb,
+    disk_kb
+FROM
+    activity_history
+WHERE
+    minute >= $1 - 59
+    AND minute <= $2
+ORDER BY
+    minute";
+        let minutes = sqlx::query_as::<_, Self>(query)
+            .bind(from)
+            .bind(to)
+            .fetch_all(sqlite)
+            .await?;
+        Ok(ActivityHistory {
+            from,
+            to,
+            minutes,
+            pauses: ModelPause::get_range(sqlite, from, to).await?,
+            sessions: ModelStats::get_range(sqlite, from, to).await?,
+        })
+    }
+}
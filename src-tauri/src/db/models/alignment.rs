@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+const MINUTES_IN_DAY: u16 = 60 * 24;
+
+/// The longest period, 12 hours, that divides a day, and whose length in seconds fits in a u16, which is how session lengths are stored
+const MAX_PERIOD_MINUTES: u16 = 60 * 12;
+
+/// Clock-aligned session options, whilst enabled each period, counted from local midnight, is work followed by a break at its end
+/// The default of a 30 minute period, with a 5 minute break, works until :25 & :55, with breaks at the top & bottom of the hour
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelAlignment {
+    pub enabled: bool,
+    pub period_minutes: u16,
+    pub break_minutes: u16,
+}
+
+impl Default for ModelAlignment {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period_minutes: 30,
+            break_minutes: 5,
+        }
+    }
+}
+
+impl ModelAlignment {
+    pub const fn get_break_sec(self) -> u16 {
+        self.break_minutes.saturating_mul(60)
+    }
+
+    pub const fn get_work_sec(self) -> u16 {
+        self.period_minutes
+            .saturating_sub(self.break_minutes)
+            .saturating_mul(60)
+    }
+
+    /// Calculate how long, in seconds, a session started at `sec_of_day`, seconds since local midnight, should last
+    /// Work ends at the start of the next break window, a break started in its window ends at the end of the period, else it lasts for the break length
+    pub const fn calc_session_sec(self, on_break: bool, sec_of_day: u32) -> u32 {
+        let period = self.period_minutes as u32 * 60;
+        let work_end = self.get_work_sec() as u32;
+        let position = sec_of_day % period;
+        if on_break {
+            if position >= work_end {
+                period - position
+            } else {
+                self.get_break_sec() as u32
+            }
+        } else if position < work_end {
+            work_end - position
+        } else {
+            period - position + work_end
+        }
+    }
+
+    /// The period must divide a day exactly, so that it stays aligned across days, and must contain both work and a break
+    pub fn validate(self) -> Result<(), AppError> {
+        if self.period_minutes < 2
+            || self.period_minutes > MAX_PERIOD_MINUTES
+            || !MINUTES_IN_DAY.is_multiple_of(self.period_minutes)
+            || self.break_minutes == 0
+            || self.break_minutes >= self.period_minutes
+        {
+            return Err(AppError::Internal(format!(
+                "Invalid alignment options {self:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get(sqlite: &SqlitePool) -> Result<Self, AppError> {
+        let query = "
+SELECT
+    enabled,
+    period_minutes,
+    break_minutes
+FROM
+    alignment";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .fetch_optional(sqlite)
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub async fn update(sqlite: &SqlitePool, alignment: &Self) -> Result<(), AppError> {
+        alignment.validate()?;
+        let query = "
+UPDATE
+    alignment
+SET
+    enabled = $1,
+    period_minutes = $2,
+    break_minutes = $3";
+        sqlx::query(query)
+            .bind(alignment.enabled)
+            .bind(alignment.period_minutes)
+            .bind(alignment.break_minutes)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{app_error::AppError, db::SessionType};
+
+/// A single work, or break, segment of a custom cycle
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelCycleSegment {
+    pub segment_type: SessionType,
+    pub duration_sec: u16,
+    pub label: Option<String>,
+}
+
+impl ModelCycleSegment {
+    /// A cycle is either empty, or alternates between work and break segments, starting with a work segment
+    pub fn validate(segments: &[Self]) -> Result<(), AppError> {
+        if !segments.len().is_multiple_of(2) || segments.len() > usize::from(u8::MAX) {
+            return Err(AppError::Internal(
+                "Cycle must contain pairs of work and break segments".to_owned(),
+            ));
+        }
+        for (index, segment) in segments.iter().enumerate() {
+            if segment.duration_sec == 0
+                || (index % 2 == 0) != (segment.segment_type == SessionType::Work)
+            {
+                return Err(AppError::Internal(format!("Invalid cycle segment {index}")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of the segment after `index`, wrapping back to the start of the cycle
+    pub fn next_index(segments: &[Self], index: u8) -> u8 {
+        if segments.is_empty() {
+            return 0;
+        }
+        u8::try_from((usize::from(index) + 1) % segments.len()).unwrap_or_default()
+    }
+
+    /// Number of work segments, starting from `index`, before the next long break, `None` if the cycle doesn't contain a long break
+    pub fn sessions_before_long(segments: &[Self], index: u8) -> Option<u8> {
+        let mut sessions = 0u8;
+        for segment in segments
+            .iter()
+            .cycle()
+            .skip(usize::from(index))
+            .take(segments.len())
+        {
+            match segment.segment_type {
+                SessionType::LongBreak => return Some(sessions),
+                SessionType::Work => sessions = sessions.saturating_add(1),
+                SessionType::ShortBreak => (),
+            }
+        }
+        None
+    }
+
+    /// Get the segments of the active profile, in order
+    pub async fn get_active(sqlite: &SqlitePool) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    segment_type,
+    duration_sec,
+    label
+FROM
+    cycle_segment
+WHERE
+    profile_id IS (SELECT profile_id FROM settings)
+ORDER BY
+    position";
+        Ok(sqlx::query_as::<_, Self>(query).fetch_all(sqlite).await?)
+    }
+
+    /// Replace the segments of the active profile, the cycle of a built in profile can't be changed
+    pub async fn set_active(sqlite: &SqlitePool, segments: &[Self]) -> Result<(), AppError> {
+        Self::validate(segments)?;
+        let query = "
+SELECT
+    profile_id
+FROM
+    profile
+WHERE
+    profile_id IS (SELECT profile_id FROM settings)
+    AND built_in = FALSE";
+        let Some(profile_id) = sqlx::query_scalar::<_, i64>(query)
+            .fetch_optional(sqlite)
+            .await?
+        else {
+            return Err(AppError::Internal(
+                "Unable to change the cycle of a built in profile".to_owned(),
+            ));
+        };
+
+        let mut transaction = sqlite.begin().await?;
+        sqlx::query("DELETE FROM cycle_segment WHERE profile_id = $1")
+            .bind(profile_id)
+            .execute(&mut *transaction)
+            .await?;
+        let query = "
+INSERT INTO
+    cycle_segment(
+    profile_id,
+    position,
+    segment_type,
+    duration_sec,
+    label
+    )
+VALUES($1, $2, $3, $4, $5)";
+        for (position, segment) in segments.iter().enumerate() {
+            sqlx::query(query)
+                .bind(profile_id)
+                .bind(i64::try_from(position).unwrap_or_default())
+                .bind(segment.segment_type)
+                .bind(segment.duration_sec)
+                .bind(segment.label.as_ref())
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Remove the segments of a deleted profile
+    pub async fn delete(sqlite: &SqlitePool, profile_id: i64) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM cycle_segment WHERE profile_id = $1")
+            .bind(profile_id)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    BreakPostponed,
+    BreakSkipped,
+    EndOfDay,
+    Overwork,
+    Suspend,
+}
+
+/// Anything, other than a work session or break, that is worth keeping a record of
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelEvent {
+    pub event_type: EventType,
+    pub started_at: i64,
+    pub duration_sec: i64,
+    pub detail: Option<String>,
+}
+
+impl ModelEvent {
+    pub async fn insert(sqlite: &SqlitePool, event: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT INTO
+    event(
+    event_type,
+    started_at,
+    duration_sec,
+    detail
+    )
+VALUES($1, $2, $3, $4)";
+        sqlx::query(query)
+            .bind(event.event_type)
+            .bind(event.started_at)
+            .bind(event.duration_sec)
+            .bind(event.detail.as_ref())
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+
+    /// Count the events of the given type that have started since the start of the current day, using localtime
+    pub async fn count_today(sqlite: &SqlitePool, event_type: EventType) -> Result<i64, AppError> {
+        let query = "
+SELECT
+    COUNT(*)
+FROM
+    event
+WHERE
+    event_type = $1
+    AND started_at >= CAST(strftime('%s', 'now', 'localtime', 'start of day', 'utc') AS INTEGER)";
+        Ok(sqlx::query_scalar(query)
+            .bind(event_type)
+            .fetch_one(sqlite)
+            .await?)
+    }
+}
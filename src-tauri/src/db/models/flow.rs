@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+/// Flow mode options, whilst enabled work sessions count up until ended, and the break length is a percentage of the work done
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelFlow {
+    pub enabled: bool,
+    pub break_percent: u8,
+    pub min_break_sec: u16,
+    pub max_break_sec: u16,
+}
+
+impl Default for ModelFlow {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            break_percent: 20,
+            min_break_sec: 300,
+            max_break_sec: 1800,
+        }
+    }
+}
+
+impl ModelFlow {
+    /// Calculate the length, in seconds, of the break earned by `work_sec` of work
+    pub fn calc_break_sec(self, work_sec: u16) -> u16 {
+        let break_sec = u32::from(work_sec) * u32::from(self.break_percent) / 100;
+        u16::try_from(break_sec)
+            .unwrap_or(u16::MAX)
+            .clamp(self.min_break_sec, self.max_break_sec)
+    }
+
+    /// The percentage must be between 1 and 100, and the maximum break can't be shorter than the minimum
+    pub fn validate(self) -> Result<(), AppError> {
+        if !(1..=100).contains(&self.break_percent)
+            || self.max_break_sec == 0
+            || self.min_break_sec > self.max_break_sec
+        {
+            return Err(AppError::Internal(format!("Invalid flow options {self:?}")));
+        }
+        Ok(())
+    }
+
+    pub async fn get(sqlite: &SqlitePool) -> Result<Self, AppError> {
+        let query = "
+SELECT
+    enabled,
+    break_percent,
+    min_break_sec,
+    max_break_sec
+FROM
+    flow";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .fetch_optional(sqlite)
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub async fn update(sqlite: &SqlitePool, flow: &Self) -> Result<(), AppError> {
+        flow.validate()?;
+        let query = "
+UPDATE
+    flow
+SET
+    enabled = $1,
+    break_percent = $2,
+    min_break_sec = $3,
+    max_break_sec = $4";
+        sqlx::query(query)
+            .bind(flow.enabled)
+            .bind(flow.break_percent)
+            .bind(flow.min_break_sec)
+            .bind(flow.max_break_sec)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
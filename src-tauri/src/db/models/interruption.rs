@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+/// Whether an interruption came from the user, or from someone, or something, else
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptionType {
+    Internal,
+    External,
+}
+
+/// A single interruption of a work session, sessions are identified by the unix timestamp, in seconds, of when they started
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelInterruption {
+    pub session_started_at: i64,
+    pub interruption_type: InterruptionType,
+    pub note: Option<String>,
+    pub created_at: i64,
+}
+
+impl ModelInterruption {
+    pub async fn insert(sqlite: &SqlitePool, interruption: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT INTO
+    interruption(
+    session_started_at,
+    interruption_type,
+    note,
+    created_at
+    )
+VALUES($1, $2, $3, $4)";
+        sqlx::query(query)
+            .bind(interruption.session_started_at)
+            .bind(interruption.interruption_type)
+            .bind(interruption.note.as_ref())
+            .bind(interruption.created_at)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+
+    /// Get every interruption of the given session, oldest first
+    pub async fn get_session(
+        sqlite: &SqlitePool,
+        session_started_at: i64,
+    ) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    session_started_at,
+    interruption_type,
+    note,
+    created_at
+FROM
+    interruption
+WHERE
+    session_started_at = $1
+ORDER BY
+    created_at,
+    interruption_id";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .bind(session_started_at)
+            .fetch_all(sqlite)
+            .await?)
+    }
+}
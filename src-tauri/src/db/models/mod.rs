@@ -1 +1,16 @@
+pub mod active_day;
+pub mod activity;
+pub mod activity_history;
+pub mod alignment;
+pub mod cycle;
+pub mod event;
+pub mod flow;
+pub mod interruption;
+pub mod pause;
+pub mod process_watch;
+pub mod profile;
+pub mod reminder;
+pub mod schedule;
 pub mod settings;
+pub mod stats;
+pub mod timer_state;
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+/// Why the timer was paused
+#[derive(
+    sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseReason {
+    /// Paused by the user
+    Manual,
+    /// Paused by the auto-pause cpu usage threshold
+    Cpu,
+    /// Paused by the working hours schedule
+    Schedule,
+    /// Paused by the user for a meeting
+    Meeting,
+    /// Paused by the pause after break option
+    AfterBreak,
+    /// Paused once the daily cap of active use has been reached
+    EndOfDay,
+    /// Paused whilst a watched process is running
+    Process,
+    /// The system was suspended, only used in the pause history, what happens after a suspension is set by the `suspend_policy` setting
+    Suspend,
+}
+
+/// What happens to the current session when the timer is resumed
+#[derive(
+    sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ResumeBehaviour {
+    /// Continue the session from where it was paused
+    Continue,
+    /// Restart the session, the time before the pause is recorded as cut short
+    Restart,
+    /// Restart the session, the time before the pause is recorded as interrupted
+    Discard,
+}
+
+/// A single pause, or suspension, that has ended
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelPause {
+    pub pause_reason: PauseReason,
+    pub started_at: i64,
+    pub duration_sec: i64,
+    pub resume_behaviour: Option<ResumeBehaviour>,
+}
+
+impl ModelPause {
+    pub async fn insert(sqlite: &SqlitePool, pause: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT INTO
+    pause_history(
+    pause_reason,
+    started_at,
+    duration_sec,
+    resume_behaviour
+    )
+VALUES($1, $2, $3, $4)";
+        sqlx::query(query)
+            .bind(pause.pause_reason)
+            .bind(pause.started_at)
+            .bind(pause.duration_sec)
+            .bind(pause.resume_behaviour)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+
+    /// Get every pause that overlaps the range, `from` & `to` are unix times
+    pub async fn get_range(sqlite: &SqlitePool, from: i64, to: i64) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    pause_reason,
+    started_at,
+    duration_sec,
+    resume_behaviour
+FROM
+    pause_history
+WHERE
+    started_at <= $2
+    AND started_at + duration_sec >= $1
+ORDER BY
+    started_at";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .bind(from)
+            .bind(to)
+            .fetch_all(sqlite)
+            .await?)
+    }
+}
+
+/// The resume behaviour of a single pause reason
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelResumeBehaviour {
+    pub pause_reason: PauseReason,
+    pub resume_behaviour: ResumeBehaviour,
+}
+
+impl ModelResumeBehaviour {
+    /// Get the behaviour for the given reason, reasons without a behaviour continue the session
+    pub fn get_behaviour(behaviours: &[Self], pause_reason: PauseReason) -> ResumeBehaviour {
+        behaviours
+            .iter()
+            .find(|i| i.pause_reason == pause_reason)
+            .map_or(ResumeBehaviour::Continue, |i| i.resume_behaviour)
+    }
+
+    pub async fn get_all(sqlite: &SqlitePool) -> Result<Vec<Self>, AppError> {
+        let query = "SELECT * FROM resume_behaviour ORDER BY pause_reason";
+        Ok(sqlx::query_as::<_, Self>(query).fetch_all(sqlite).await?)
+    }
+
+    /// Insert, or replace, the behaviour of a single pause reason
+    pub async fn set(sqlite: &SqlitePool, behaviour: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT OR REPLACE INTO
+    resume_behaviour(
+    pause_reason,
+    resume_behaviour
+    )
+VALUES($1, $2)";
+        sqlx::query(query)
+            .bind(behaviour.pause_reason)
+            .bind(behaviour.resume_behaviour)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+/// A process that pauses the timer whilst it is running
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelProcessWatch {
+    /// Either a process name, matched case-insensitively, and with or without an `.exe` extension, or a regex, matched anywhere in the process name
+    pub pattern: String,
+    pub is_regex: bool,
+    pub enabled: bool,
+}
+
+impl ModelProcessWatch {
+    /// Convert the pattern into a regex, a process name is escaped, and must match the entire process name
+    pub fn to_regex(&self) -> Result<Regex, AppError> {
+        let pattern = if self.is_regex {
+            self.pattern.clone()
+        } else {
+            format!("(?i)^{}(\\.exe)?$", regex::escape(self.pattern.trim()))
+        };
+        Regex::new(&pattern)
+            .map_err(|_| AppError::Internal(format!("Invalid process pattern {}", self.pattern)))
+    }
+
+    pub async fn get_all(sqlite: &SqlitePool) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    pattern,
+    is_regex,
+    enabled
+FROM
+    process_watch
+ORDER BY
+    process_watch_id";
+        Ok(sqlx::query_as::<_, Self>(query).fetch_all(sqlite).await?)
+    }
+
+    /// Replace all process watches, every pattern must be a valid regex, or a non-empty process name
+    pub async fn set_all(sqlite: &SqlitePool, process_watches: &[Self]) -> Result<(), AppError> {
+        for process_watch in process_watches {
+            if process_watch.pattern.trim().is_empty() {
+                return Err(AppError::Internal("Empty process pattern".to_owned()));
+            }
+            process_watch.to_regex()?;
+        }
+        let mut transaction = sqlite.begin().await?;
+        sqlx::query("DELETE FROM process_watch")
+            .execute(&mut *transaction)
+            .await?;
+        let query = "
+INSERT INTO
+    process_watch(
+    pattern,
+    is_regex,
+    enabled
+    )
+VALUES($1, $2, $3)";
+        for process_watch in process_watches {
+            sqlx::query(query)
+                .bind(&process_watch.pattern)
+                .bind(process_watch.is_regex)
+                .bind(process_watch.enabled)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+}
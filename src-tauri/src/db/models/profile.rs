@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{
+    app_error::AppError,
+    db::{ModelCycleSegment, ModelSettings},
+};
+
+/// Name of the profile created from the existing settings, when no profile is active
+const INITIAL_PROFILE_NAME: &str = "Default";
+
+/// A named set of session, break, and auto-pause settings
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelProfile {
+    pub profile_id: i64,
+    pub name: String,
+    pub built_in: bool,
+    pub auto_pause: bool,
+    pub auto_pause_threshold: u8,
+    pub auto_pause_timespan_sec: u16,
+    pub auto_resume: bool,
+    pub auto_resume_threshold: u8,
+    pub auto_resume_timespan_sec: u16,
+    pub long_break_as_sec: u16,
+    pub number_session_before_break: u8,
+    pub session_as_sec: u16,
+    pub short_break_as_sec: u16,
+}
+
+/// All profiles, and the id of the profile that the settings are currently a copy of
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub active_profile_id: Option<i64>,
+    pub profiles: Vec<ModelProfile>,
+}
+
+impl ModelProfile {
+    /// Copy the values of the profile into the given settings, settings that aren't part of a profile are left unchanged
+    pub const fn apply(&self, settings: ModelSettings) -> ModelSettings {
+        ModelSettings {
+            auto_pause: self.auto_pause,
+            auto_pause_threshold: self.auto_pause_threshold,
+            auto_pause_timespan_sec: self.auto_pause_timespan_sec,
+            auto_resume: self.auto_resume,
+            auto_resume_threshold: self.auto_resume_threshold,
+            auto_resume_timespan_sec: self.auto_resume_timespan_sec,
+            long_break_as_sec: self.long_break_as_sec,
+            number_session_before_break: self.number_session_before_break,
+            session_as_sec: self.session_as_sec,
+            short_break_as_sec: self.short_break_as_sec,
+            ..settings
+        }
+    }
+
+    /// If no profile is active, which is the case on first run, or after upgrading, save the current settings as a new profile, and make it active
+    pub async fn init(sqlite: &SqlitePool, settings: &ModelSettings) -> Result<(), AppError> {
+        if Self::get_active_id(sqlite).await?.is_some() {
+            return Ok(());
+        }
+        let query = "
+INSERT OR REPLACE INTO
+    profile(
+    name,
+    auto_pause,
+    auto_pause_threshold,
+    auto_pause_timespan_sec,
+    auto_resume,
+    auto_resume_threshold,
+    auto_resume_timespan_sec,
+    long_break_as_sec,
+    number_session_before_break,
+    session_as_sec,
+    short_break_as_sec
+    )
+VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+        let profile_id =
+            Self::bind_settings(sqlx::query(query).bind(INITIAL_PROFILE_NAME), settings)
+                .execute(sqlite)
+                .await?
+                .last_insert_rowid();
+        Self::set_active(sqlite, profile_id).await
+    }
+
+    /// Bind, in order, the profile values of the given settings to a query
+    fn bind_settings<'q>(
+        query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+        settings: &ModelSettings,
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+        query
+            .bind(settings.auto_pause)
+            .bind(settings.auto_pause_threshold)
+            .bind(settings.auto_pause_timespan_sec)
+            .bind(settings.auto_resume)
+            .bind(settings.auto_resume_threshold)
+            .bind(settings.auto_resume_timespan_sec)
+            .bind(settings.long_break_as_sec)
+            .bind(settings.number_session_before_break)
+            .bind(settings.session_as_sec)
+            .bind(settings.short_break_as_sec)
+    }
+
+    /// Get every profile, built in profiles first
+    pub async fn get_all(sqlite: &SqlitePool) -> Result<Profiles, AppError> {
+        let query = "
+SELECT
+    *
+FROM
+    profile
+ORDER BY
+    built_in DESC,
+    profile_id";
+        let profiles = sqlx::query_as::<_, Self>(query).fetch_all(sqlite).await?;
+        Ok(Profiles {
+            active_profile_id: Self::get_active_id(sqlite).await?,
+            profiles,
+        })
+    }
+
+    pub async fn get(sqlite: &SqlitePool, profile_id: i64) -> Result<Option<Self>, AppError> {
+        let query = "SELECT * FROM profile WHERE profile_id = $1";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .bind(profile_id)
+            .fetch_optional(sqlite)
+            .await?)
+    }
+
+    async fn get_active_id(sqlite: &SqlitePool) -> Result<Option<i64>, AppError> {
+        let query = "SELECT profile_id FROM settings";
+        Ok(sqlx::query_scalar::<_, Option<i64>>(query)
+            .fetch_optional(sqlite)
+            .await?
+            .flatten())
+    }
+
+    /// Create a user profile from the given settings, and make it the active profile
+    pub async fn create(
+        sqlite: &SqlitePool,
+        name: &str,
+        settings: &ModelSettings,
+    ) -> Result<(), AppError> {
+        let query = "
+INSERT INTO
+    profile(
+    name,
+    auto_pause,
+    auto_pause_threshold,
+    auto_pause_timespan_sec,
+    auto_resume,
+    auto_resume_threshold,
+    auto_resume_timespan_sec,
+    long_break_as_sec,
+    number_session_before_break,
+    session_as_sec,
+    short_break_as_sec
+    )
+VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+        let profile_id = Self::bind_settings(sqlx::query(query).bind(name.trim()), settings)
+            .execute(sqlite)
+            .await?
+            .last_insert_rowid();
+        Self::set_active(sqlite, profile_id).await
+    }
+
+    /// Rename a user profile, built in profiles can't be renamed
+    pub async fn rename(sqlite: &SqlitePool, profile_id: i64, name: &str) -> Result<(), AppError> {
+        let query = "
+UPDATE
+    profile
+SET
+    name = $1
+WHERE
+    profile_id = $2
+    AND built_in = FALSE";
+        let result = sqlx::query(query)
+            .bind(name.trim())
+            .bind(profile_id)
+            .execute(sqlite)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::Internal(format!(
+                "Unable to rename profile {profile_id}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Delete a user profile, built in profiles, and the active profile, can't be deleted
+    pub async fn delete(sqlite: &SqlitePool, profile_id: i64) -> Result<(), AppError> {
+        let query = "
+DELETE FROM
+    profile
+WHERE
+    profile_id = $1
+    AND built_in = FALSE
+    AND profile_id IS NOT (SELECT profile_id FROM settings)";
+        let result = sqlx::query(query).bind(profile_id).execute(sqlite).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::Internal(format!(
+                "Unable to delete profile {profile_id}"
+            )));
+        }
+        ModelCycleSegment::delete(sqlite, profile_id).await
+    }
+
+    /// Set the profile that the settings are a copy of
+    pub async fn set_active(sqlite: &SqlitePool, profile_id: i64) -> Result<(), AppError> {
+        let query = "UPDATE settings SET profile_id = $1";
+        sqlx::query(query).bind(profile_id).execute(sqlite).await?;
+        Ok(())
+    }
+
+    /// Save changed settings into the active profile, unless it's a built in profile
+    pub async fn update_active(
+        sqlite: &SqlitePool,
+        settings: &ModelSettings,
+    ) -> Result<(), AppError> {
+        let query = "
+UPDATE
+    profile
+SET
+    auto_pause = $1,
+    auto_pause_threshold = $2,
+    auto_pause_timespan_sec = $3,
+    auto_resume = $4,
+    auto_resume_threshold = $5,
+    auto_resume_timespan_sec = $6,
+    long_break_as_sec = $7,
+    number_session_before_break = $8,
+    session_as_sec = $9,
+    short_break_as_sec = $10
+WHERE
+    profile_id IS (SELECT profile_id FROM settings)
+    AND built_in = FALSE";
+        Self::bind_settings(sqlx::query(query), settings)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+const MINUTES_IN_DAY: u16 = 60 * 24;
+
+/// How a reminder is shown when it is due
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderDisplay {
+    /// Shown next to the systemtray icon
+    Notification,
+    /// Shown in the main window, without going fullscreen
+    Overlay,
+}
+
+/// A recurring reminder, independent of the Pomodoro cycle, minutes are since the start of the day, `end_minute` is exclusive
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelReminder {
+    pub label: String,
+    pub interval_sec: u32,
+    /// How long the reminder is shown for
+    pub duration_sec: u16,
+    pub display_mode: ReminderDisplay,
+    pub start_minute: u16,
+    pub end_minute: u16,
+    /// Whether the reminder stops counting down whilst the timer is paused
+    pub pause_with_timer: bool,
+    pub enabled: bool,
+}
+
+impl ModelReminder {
+    /// Check if the given localtime minute is inside the active hours of the reminder
+    pub fn is_active(&self, minute: u16) -> bool {
+        self.enabled && (self.start_minute..self.end_minute).contains(&minute)
+    }
+
+    /// The interval and label can't be empty, and the active hours must end after they start, on the same day
+    fn validate(&self) -> Result<(), AppError> {
+        if self.label.trim().is_empty()
+            || self.interval_sec == 0
+            || self.start_minute >= self.end_minute
+            || self.end_minute > MINUTES_IN_DAY
+        {
+            return Err(AppError::Internal(format!("Invalid reminder {self:?}")));
+        }
+        Ok(())
+    }
+
+    pub async fn get_all(sqlite: &SqlitePool) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    label,
+    interval_sec,
+    duration_sec,
+    display_mode,
+    start_minute,
+    end_minute,
+    pause_with_timer,
+    enabled
+FROM
+    reminder
+ORDER BY
+    reminder_id";
+        Ok(sqlx::query_as::<_, Self>(query).fetch_all(sqlite).await?)
+    }
+
+    /// Replace all reminders
+    pub async fn set_all(sqlite: &SqlitePool, reminders: &[Self]) -> Result<(), AppError> {
+        for reminder in reminders {
+            reminder.validate()?;
+        }
+        let mut transaction = sqlite.begin().await?;
+        sqlx::query("DELETE FROM reminder")
+            .execute(&mut *transaction)
+            .await?;
+        let query = "
+INSERT INTO
+    reminder(
+    label,
+    interval_sec,
+    duration_sec,
+    display_mode,
+    start_minute,
+    end_minute,
+    pause_with_timer,
+    enabled
+    )
+VALUES($1, $2, $3, $4, $5, $6, $7, $8)";
+        for reminder in reminders {
+            sqlx::query(query)
+                .bind(reminder.label.trim())
+                .bind(reminder.interval_sec)
+                .bind(reminder.duration_sec)
+                .bind(reminder.display_mode)
+                .bind(reminder.start_minute)
+                .bind(reminder.end_minute)
+                .bind(reminder.pause_with_timer)
+                .bind(reminder.enabled)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::app_error::AppError;
+
+const MINUTES_IN_DAY: u16 = 60 * 24;
+
+/// How many days ahead to look for the next working day
+const NEXT_WORKING_DAY_LIMIT: usize = 14;
+
+/// A single window of working hours, weekday 0 is Sunday, minutes are since the start of the day, `end_minute` is exclusive
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleWindow {
+    pub weekday: u8,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+/// A date, as `YYYY-MM-DD`, which is entirely outside of working hours
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleException {
+    pub date: String,
+    pub label: Option<String>,
+}
+
+/// The working hours schedule, without any windows the schedule is disabled
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelSchedule {
+    pub windows: Vec<ScheduleWindow>,
+    pub exceptions: Vec<ScheduleException>,
+}
+
+impl ModelSchedule {
+    /// Check if the given localtime is inside working hours, always true if the schedule is disabled
+    pub fn is_working(&self, weekday: u8, minute: u16, date: &str) -> bool {
+        if self.windows.is_empty() {
+            return true;
+        }
+        if self.exceptions.iter().any(|i| i.date == date) {
+            return false;
+        }
+        self.windows
+            .iter()
+            .any(|i| i.weekday == weekday && (i.start_minute..i.end_minute).contains(&minute))
+    }
+
+    /// Get the start of the first window on the next working day after `today`, `None` if the schedule is disabled, or there isn't a working day in the next two weeks
+    pub fn get_next_working_day(&self, today: chrono::NaiveDate) -> Option<chrono::NaiveDateTime> {
+        today
+            .iter_days()
+            .skip(1)
+            .take(NEXT_WORKING_DAY_LIMIT)
+            .filter(|date| {
+                let date = date.format("%Y-%m-%d").to_string();
+                !self.exceptions.iter().any(|i| i.date == date)
+            })
+            .find_map(|date| {
+                let weekday =
+                    u8::try_from(date.weekday().num_days_from_sunday()).unwrap_or_default();
+                self.windows
+                    .iter()
+                    .filter(|i| i.weekday == weekday)
+                    .map(|i| i.start_minute)
+                    .min()
+                    .and_then(|minute| {
+                        date.and_hms_opt(u32::from(minute / 60), u32::from(minute % 60), 0)
+                    })
+            })
+    }
+
+    /// Windows must be on a valid weekday, and end after they start, on the same day, exceptions must be valid dates
+    pub fn validate(&self) -> Result<(), AppError> {
+        for window in &self.windows {
+            if window.weekday > 6
+                || window.start_minute >= window.end_minute
+                || window.end_minute > MINUTES_IN_DAY
+            {
+                return Err(AppError::Internal(format!(
+                    "Invalid schedule window {window:?}"
+                )));
+            }
+        }
+        for exception in &self.exceptions {
+            if chrono::NaiveDate::parse_from_str(&exception.date, "%Y-%m-%d").is_err() {
+                return Err(AppError::Internal(format!(
+                    "Invalid schedule exception date {}",
+                    exception.date
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn get(sqlite: &SqlitePool) -> Result<Self, AppError> {
+        let query = "
+SELECT
+    weekday,
+    start_minute,
+    end_minute
+FROM
+    schedule_window
+ORDER BY
+    weekday,
+    start_minute";
+        let windows = sqlx::query_as::<_, ScheduleWindow>(query)
+            .fetch_all(sqlite)
+            .await?;
+        let query = "
+SELECT
+    date,
+    label
+FROM
+    schedule_exception
+ORDER BY
+    date";
+        let exceptions = sqlx::query_as::<_, ScheduleException>(query)
+            .fetch_all(sqlite)
+            .await?;
+        Ok(Self {
+            windows,
+            exceptions,
+        })
+    }
+
+    /// Replace the entire schedule
+    pub async fn set(sqlite: &SqlitePool, schedule: &Self) -> Result<(), AppError> {
+        schedule.validate()?;
+        let mut transaction = sqlite.begin().await?;
+        sqlx::query("DELETE FROM schedule_window")
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM schedule_exception")
+            .execute(&mut *transaction)
+            .await?;
+        let query = "
+INSERT INTO
+    schedule_window(
+    weekday,
+    start_minute,
+    end_minute
+    )
+VALUES($1, $2, $3)";
+        for window in &schedule.windows {
+            sqlx::query(query)
+                .bind(window.weekday)
+                .bind(window.start_minute)
+                .bind(window.end_minute)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        let query = "
+INSERT OR REPLACE INTO
+    schedule_exception(
+    date,
+    label
+    )
+VALUES($1, $2)";
+        for exception in &schedule.exceptions {
+            sqlx::query(query)
+                .bind(&exception.date)
+                .bind(exception.label.as_ref())
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+}
@@ -4,6 +4,32 @@ use sqlx::{FromRow, SqlitePool};
 use crate::{app_error::AppError, request_handlers::FrontEndState};
 
 const ONE_MINUTE_AS_SEC: u16 = 60;
+const ONE_HOUR_AS_SEC: u32 = 60 * 60;
+
+/// What to do with the timer after the system has been suspended
+#[derive(
+    sqlx::Type, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SuspendPolicy {
+    /// Treat the time suspended as a break, a long enough suspension will also reset the long break count
+    BreakTaken,
+    /// Restart the current session from the beginning
+    Restart,
+    /// Continue the current session from where it was when suspended
+    Resume,
+}
+
+impl std::fmt::Display for SuspendPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BreakTaken => write!(f, "break_taken"),
+            Self::Restart => write!(f, "restart"),
+            Self::Resume => write!(f, "resume"),
+        }
+    }
+}
 
 #[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
 #[allow(clippy::struct_excessive_bools)]
@@ -14,11 +40,19 @@ pub struct ModelSettings {
     pub auto_resume_threshold: u8,
     pub auto_resume_timespan_sec: u16,
     pub auto_resume: bool,
+    pub break_warning_sec: u16,
+    pub carry_over_skipped: bool,
+    pub daily_cap_sec: u32,
     pub fullscreen: bool,
     pub long_break_as_sec: u16,
     pub number_session_before_break: u8,
+    pub overwork_limit_sec: u16,
+    pub postpone_limit_per_day: u8,
+    pub ready_after_break: bool,
+    pub restore_max_age_sec: u32,
     pub session_as_sec: u16,
     pub short_break_as_sec: u16,
+    pub suspend_policy: SuspendPolicy,
 }
 
 impl From<&FrontEndState> for ModelSettings {
@@ -30,11 +64,19 @@ impl From<&FrontEndState> for ModelSettings {
             auto_resume_threshold: value.auto_resume_threshold,
             auto_resume_timespan_sec: value.auto_resume_timespan_sec,
             auto_resume: value.auto_resume,
+            break_warning_sec: value.break_warning_sec,
+            carry_over_skipped: value.carry_over_skipped,
+            daily_cap_sec: value.daily_cap_sec,
             fullscreen: value.fullscreen,
             long_break_as_sec: value.long_break_as_sec,
             number_session_before_break: value.number_session_before_break,
+            overwork_limit_sec: value.overwork_limit_sec,
+            postpone_limit_per_day: value.postpone_limit_per_day,
+            ready_after_break: value.ready_after_break,
+            restore_max_age_sec: value.restore_max_age_sec,
             session_as_sec: value.session_as_sec,
             short_break_as_sec: value.short_break_as_sec,
+            suspend_policy: value.suspend_policy,
         }
     }
 }
@@ -48,11 +90,19 @@ impl ModelSettings {
             auto_resume_threshold: 5,
             auto_resume_timespan_sec: 300,
             auto_resume: false,
+            break_warning_sec: 30,
+            carry_over_skipped: false,
+            daily_cap_sec: 0,
             fullscreen: false,
             long_break_as_sec: ONE_MINUTE_AS_SEC * 5,
             number_session_before_break: 4,
+            overwork_limit_sec: 0,
+            postpone_limit_per_day: 3,
+            ready_after_break: false,
+            restore_max_age_sec: ONE_HOUR_AS_SEC,
             session_as_sec: ONE_MINUTE_AS_SEC * 25,
             short_break_as_sec: ONE_MINUTE_AS_SEC,
+            suspend_policy: SuspendPolicy::Resume,
         }
     }
 
@@ -70,8 +120,16 @@ SET
     fullscreen = $7,
     long_break_as_sec = $8,
     number_session_before_break = $9,
-    session_as_sec = $10,
-    short_break_as_sec = $11";
+    restore_max_age_sec = $10,
+    session_as_sec = $11,
+    short_break_as_sec = $12,
+    suspend_policy = $13,
+    postpone_limit_per_day = $14,
+    carry_over_skipped = $15,
+    break_warning_sec = $16,
+    ready_after_break = $17,
+    overwork_limit_sec = $18,
+    daily_cap_sec = $19";
         sqlx::query(query)
             .bind(settings.auto_pause_threshold)
             .bind(settings.auto_pause_timespan_sec)
@@ -82,8 +140,16 @@ SET
             .bind(settings.fullscreen)
             .bind(settings.long_break_as_sec)
             .bind(settings.number_session_before_break)
+            .bind(settings.restore_max_age_sec)
             .bind(settings.session_as_sec)
             .bind(settings.short_break_as_sec)
+            .bind(settings.suspend_policy)
+            .bind(settings.postpone_limit_per_day)
+            .bind(settings.carry_over_skipped)
+            .bind(settings.break_warning_sec)
+            .bind(settings.ready_after_break)
+            .bind(settings.overwork_limit_sec)
+            .bind(settings.daily_cap_sec)
             .execute(sqlite)
             .await?;
         Ok(())
@@ -112,10 +178,18 @@ INSERT INTO
     fullscreen,
     long_break_as_sec,
     number_session_before_break,
+    restore_max_age_sec,
     session_as_sec,
-    short_break_as_sec
+    short_break_as_sec,
+    suspend_policy,
+    postpone_limit_per_day,
+    carry_over_skipped,
+    break_warning_sec,
+    ready_after_break,
+    overwork_limit_sec,
+    daily_cap_sec
     )
-VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)";
         sqlx::query(query)
             .bind(settings.auto_pause_threshold)
             .bind(settings.auto_pause_timespan_sec)
@@ -126,8 +200,16 @@ VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
             .bind(settings.fullscreen)
             .bind(settings.long_break_as_sec)
             .bind(settings.number_session_before_break)
+            .bind(settings.restore_max_age_sec)
             .bind(settings.session_as_sec)
             .bind(settings.short_break_as_sec)
+            .bind(settings.suspend_policy)
+            .bind(settings.postpone_limit_per_day)
+            .bind(settings.carry_over_skipped)
+            .bind(settings.break_warning_sec)
+            .bind(settings.ready_after_break)
+            .bind(settings.overwork_limit_sec)
+            .bind(settings.daily_cap_sec)
             .execute(sqlite)
             .await?;
         Ok(settings)
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Timelike};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{
+    app_error::AppError,
+    application_state::{BreakVariant, SessionStatus},
+};
+
+/// Get the current time as seconds since the unix epoch
+pub fn now_as_sec() -> i64 {
+    i64::try_from(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    )
+    .unwrap_or_default()
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SessionType {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl From<SessionStatus> for SessionType {
+    fn from(value: SessionStatus) -> Self {
+        match value {
+            SessionStatus::Work | SessionStatus::Flow => Self::Work,
+            SessionStatus::Break(BreakVariant::Short) => Self::ShortBreak,
+            SessionStatus::Break(BreakVariant::Long) => Self::LongBreak,
+        }
+    }
+}
+
+impl From<SessionType> for SessionStatus {
+    fn from(value: SessionType) -> Self {
+        match value {
+            SessionType::Work => Self::Work,
+            SessionType::ShortBreak => Self::Break(BreakVariant::Short),
+            SessionType::LongBreak => Self::Break(BreakVariant::Long),
+        }
+    }
+}
+
+/// How a session came to an end
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SessionOutcome {
+    /// Ran for the full planned duration without being paused
+    Completed,
+    /// Ran for the full planned duration, but was paused at least once
+    Paused,
+    /// Ended before the planned duration was reached
+    CutShort,
+    /// Discarded when resuming from a pause, not counted as focus time
+    Interrupted,
+    /// A break taken whilst paused, or idle, that was long enough to count as a short or long break
+    Natural,
+}
+
+/// The grouping used when calculating stats totals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsPeriod {
+    /// `strftime` format used to group sessions, weeks start on a Monday
+    const fn get_format(self) -> &'static str {
+        match self {
+            Self::Day => "%Y-%m-%d",
+            Self::Week => "%Y-W%W",
+            Self::Month => "%Y-%m",
+        }
+    }
+
+    /// `datetime` modifiers, applied to the current localtime, to get the start of the range of returned totals, 30 days, 12 weeks, or 12 months
+    fn get_range_modifiers(self) -> Vec<&'static str> {
+        match self {
+            Self::Day => vec!["start of day", "-29 days"],
+            // "weekday 0" moves forward to the Sunday that ends the current week, unless already a Sunday, 6 days before that is the Monday that starts it, then back a further 11 weeks
+            Self::Week => vec!["start of day", "weekday 0", "-6 days", "-77 days"],
+            Self::Month => vec!["start of month", "-11 months"],
+        }
+    }
+}
+
+/// Totals for a single day, week, or month
+#[derive(FromRow, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsTotal {
+    pub period: String,
+    pub focus_sec: i64,
+    pub sessions_completed: i64,
+    pub short_breaks: i64,
+    pub long_breaks: i64,
+    pub breaks_skipped: i64,
+    pub breaks_postponed: i64,
+    pub interruptions: i64,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsTotals {
+    pub period: StatsPeriod,
+    pub totals: Vec<StatsTotal>,
+}
+
+/// Focus time for a single hour of a weekday, weekday 0 is Sunday
+/// The focus time of a session is split across every hour it spans, `sessions` is the number of sessions that started in the hour
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsHeatmap {
+    pub weekday: u8,
+    pub hour: u8,
+    pub focus_sec: i64,
+    pub sessions: i64,
+}
+
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelStats {
+    pub session_type: SessionType,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub planned_sec: u16,
+    pub actual_sec: u32,
+    pub outcome: SessionOutcome,
+}
+
+impl ModelStats {
+    /// Insert a single finished session, along with the number of interruptions logged during it
+    pub async fn insert(sqlite: &SqlitePool, stats: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT INTO
+    stats(
+    session_type,
+    started_at,
+    ended_at,
+    planned_sec,
+    actual_sec,
+    outcome,
+    internal_interruptions,
+    external_interruptions
+    )
+VALUES(
+    $1, $2, $3, $4, $5, $6,
+    (SELECT COUNT(*) FROM interruption WHERE session_started_at = $2 AND interruption_type = 'internal'),
+    (SELECT COUNT(*) FROM interruption WHERE session_started_at = $2 AND interruption_type = 'external')
+)";
+        sqlx::query(query)
+            .bind(stats.session_type)
+            .bind(stats.started_at)
+            .bind(stats.ended_at)
+            .bind(stats.planned_sec)
+            .bind(stats.actual_sec)
+            .bind(stats.outcome)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the focus, session, and break totals, grouped by the given period, using localtime
+    pub async fn get_totals(
+        sqlite: &SqlitePool,
+        period: StatsPeriod,
+    ) -> Result<StatsTotals, AppError> {
+        let modifiers = period.get_range_modifiers();
+        let placeholders = (2..)
+            .take(modifiers.len())
+            .map(|i| format!("${i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            "
+WITH range AS (
+    SELECT CAST(strftime('%s', 'now', 'localtime', {placeholders}, 'utc') AS INTEGER) AS since
+),
+entries AS (
+    SELECT
+        started_at, session_type, actual_sec, outcome, NULL AS event_type,
+        internal_interruptions + external_interruptions AS interruptions
+    FROM stats WHERE outcome != 'interrupted'
+    UNION ALL
+    SELECT started_at, NULL, 0, NULL, event_type, 0 FROM event
+)
+SELECT
+    strftime($1, started_at, 'unixepoch', 'localtime') AS period,
+    COALESCE(SUM(CASE WHEN session_type = 'work' THEN actual_sec END), 0) AS focus_sec,
+    COUNT(CASE WHEN session_type = 'work' AND outcome IN ('completed', 'paused') THEN 1 END) AS sessions_completed,
+    COUNT(CASE WHEN session_type = 'short_break' THEN 1 END) AS short_breaks,
+    COUNT(CASE WHEN session_type = 'long_break' THEN 1 END) AS long_breaks,
+    COUNT(CASE WHEN event_type = 'break_skipped' THEN 1 END) AS breaks_skipped,
+    COUNT(CASE WHEN event_type = 'break_postponed' THEN 1 END) AS breaks_postponed,
+    SUM(interruptions) AS interruptions
+FROM
+    entries
+WHERE
+    started_at >= (SELECT since FROM range)
+GROUP BY
+    period
+ORDER BY
+    period"
+        );
+        let totals = modifiers
+            .into_iter()
+            .fold(
+                sqlx::query_as::<_, StatsTotal>(&query).bind(period.get_format()),
+                sqlx::query::QueryAs::bind,
+            )
+            .fetch_all(sqlite)
+            .await?;
+        Ok(StatsTotals { period, totals })
+    }
+
+    /// Get every session that overlaps the range, `from` & `to` are unix times
+    pub async fn get_range(sqlite: &SqlitePool, from: i64, to: i64) -> Result<Vec<Self>, AppError> {
+        let query = "
+SELECT
+    session_type,
+    started_at,
+    ended_at,
+    planned_sec,
+    actual_sec,
+    outcome
+FROM
+    stats
+WHERE
+    started_at <= $2
+    AND ended_at >= $1
+ORDER BY
+    started_at";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .bind(from)
+            .bind(to)
+            .fetch_all(sqlite)
+            .await?)
+    }
+
+    /// Get the focus time of all work sessions, grouped by weekday and hour of day, using localtime
+    /// The focus time is counted from the start of the session, so any time spent paused isn't placed in the hours it happened
+    pub async fn get_heatmap(sqlite: &SqlitePool) -> Result<Vec<StatsHeatmap>, AppError> {
+        let query = "
+SELECT
+    started_at,
+    actual_sec
+FROM
+    stats
+WHERE
+    session_type = 'work'
+    AND outcome != 'interrupted'";
+        let sessions = sqlx::query_as::<_, (i64, i64)>(query)
+            .fetch_all(sqlite)
+            .await?;
+        let mut heatmap = BTreeMap::new();
+        for (started_at, actual_sec) in sessions {
+            let mut at = started_at;
+            let mut remaining_sec = actual_sec;
+            let mut first = true;
+            while remaining_sec > 0 || first {
+                let Some(local) = chrono::DateTime::from_timestamp(at, 0)
+                    .map(|i| i.with_timezone(&chrono::Local))
+                else {
+                    break;
+                };
+                let into_hour = i64::from(local.minute() * 60 + local.second());
+                let chunk_sec = remaining_sec.min(3600 - into_hour);
+                let entry = heatmap
+                    .entry((local.weekday().num_days_from_sunday(), local.hour()))
+                    .or_insert((0, 0));
+                entry.0 += chunk_sec;
+                if first {
+                    entry.1 += 1;
+                    first = false;
+                }
+                at += chunk_sec;
+                remaining_sec -= chunk_sec;
+            }
+        }
+        Ok(heatmap
+            .into_iter()
+            .map(|((weekday, hour), (focus_sec, sessions))| StatsHeatmap {
+                weekday: u8::try_from(weekday).unwrap_or_default(),
+                hour: u8::try_from(hour).unwrap_or_default(),
+                focus_sec,
+                sessions,
+            })
+            .collect())
+    }
+}
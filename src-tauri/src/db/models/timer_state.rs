@@ -0,0 +1,68 @@
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{
+    app_error::AppError,
+    db::{PauseReason, SessionType},
+};
+
+/// Snapshot of the current timer, so that the Pomodoro cycle can survive a restart or crash
+#[derive(FromRow, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ModelTimerState {
+    pub session_type: SessionType,
+    pub planned_sec: u16,
+    pub remaining_sec: u16,
+    pub session_count: u8,
+    pub cycle_index: u8,
+    pub paused: bool,
+    pub pause_reason: Option<PauseReason>,
+    pub resume_at: Option<i64>,
+    pub pause_after_break: bool,
+    pub session_started_at: i64,
+    pub updated_at: i64,
+}
+
+impl ModelTimerState {
+    /// Get the snapshot, if one has ever been saved
+    pub async fn get(sqlite: &SqlitePool) -> Result<Option<Self>, AppError> {
+        let query = "SELECT * FROM timer_state";
+        Ok(sqlx::query_as::<_, Self>(query)
+            .fetch_optional(sqlite)
+            .await?)
+    }
+
+    /// Insert, or replace, the single snapshot row
+    pub async fn upsert(sqlite: &SqlitePool, timer_state: &Self) -> Result<(), AppError> {
+        let query = "
+INSERT OR REPLACE INTO
+    timer_state(
+    timer_state_id,
+    session_type,
+    planned_sec,
+    remaining_sec,
+    session_count,
+    cycle_index,
+    paused,
+    pause_reason,
+    resume_at,
+    pause_after_break,
+    session_started_at,
+    updated_at
+    )
+VALUES(1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+        sqlx::query(query)
+            .bind(timer_state.session_type)
+            .bind(timer_state.planned_sec)
+            .bind(timer_state.remaining_sec)
+            .bind(timer_state.session_count)
+            .bind(timer_state.cycle_index)
+            .bind(timer_state.paused)
+            .bind(timer_state.pause_reason)
+            .bind(timer_state.resume_at)
+            .bind(timer_state.pause_after_break)
+            .bind(timer_state.session_started_at)
+            .bind(timer_state.updated_at)
+            .execute(sqlite)
+            .await?;
+        Ok(())
+    }
+}
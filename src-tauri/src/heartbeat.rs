@@ -1,31 +1,68 @@
 use std::sync::Arc;
 use tokio::sync::broadcast::Sender;
 
-use crate::message_handler::MsgI;
+use crate::{
+    activity::{ActivitySampler, PROC_ROOT, ProcessSampler},
+    db::CpuMode,
+    message_handler::{MsgI, Suspension},
+};
+
+/// How often, in seconds, to check the running processes
+const PROCESS_CHECK_SEC: u64 = 5;
+
+/// A gap between two heartbeats, in seconds, greater than this is treated as the system having been suspended
+const SUSPEND_THRESHOLD_SEC: u64 = 30;
+
+/// Compare the current instant & wall-clock with those from the previous heartbeat, and return a Suspension if the gap is large enough
+fn check_suspension(previous: (std::time::Instant, std::time::SystemTime)) -> Option<Suspension> {
+    let wall = std::time::SystemTime::now()
+        .duration_since(previous.1)
+        .unwrap_or_default();
+    let instant = previous.0.elapsed();
+    (wall.max(instant).as_secs() >= SUSPEND_THRESHOLD_SEC).then_some(Suspension { wall, instant })
+}
 
 /// Spawn off a tokio thread, that loops continually, well with a 250ms pause between each loop
-/// The outer tread is saved into ApplicationState, so that it can be cancelled at any time
-pub fn heartbeat_process(sx: &Sender<MsgI>) {
+/// The outer tread is saved into ApplicationState, so that it can be cancelled at any time, and is replaced whenever the cpu measurement changes
+pub fn heartbeat_process(sx: &Sender<MsgI>, cpu_mode: CpuMode, cpu_processes: Vec<String>) {
     let (sx, thread_sx) = (sx.clone(), sx.clone());
     let heartbeat_process = Arc::new(tokio::task::spawn(async move {
-        let mut sys = sysinfo::System::new();
+        let mut sampler =
+            ActivitySampler::new(std::path::Path::new(PROC_ROOT), cpu_mode, &cpu_processes);
+        let mut process_sampler = ProcessSampler::new();
+        let mut process_instant = std::time::Instant::now();
         let mut loop_instant = std::time::Instant::now();
         let mut cpu_instant = std::time::Instant::now();
+        let mut previous_tick = (std::time::Instant::now(), std::time::SystemTime::now());
 
         loop {
-            let cpu_usage = if cpu_instant.elapsed().as_millis() >= 1000 {
-                sys.refresh_cpu_usage();
-                let cpu_usage = sys.global_cpu_usage();
+            if let Some(suspension) = check_suspension(previous_tick) {
+                thread_sx
+                    .send(MsgI::HeartBeat(crate::message_handler::MsgHB::Suspended(
+                        suspension,
+                    )))
+                    .ok();
+            }
+            previous_tick = (std::time::Instant::now(), std::time::SystemTime::now());
+            let activity = if cpu_instant.elapsed().as_millis() >= 1000 {
                 cpu_instant = std::time::Instant::now();
-                Some(cpu_usage)
+                Some(sampler.sample())
             } else {
                 None
             };
             thread_sx
                 .send(MsgI::HeartBeat(crate::message_handler::MsgHB::OnHeartbeat(
-                    cpu_usage,
+                    activity,
                 )))
                 .ok();
+            if process_instant.elapsed().as_secs() >= PROCESS_CHECK_SEC {
+                process_instant = std::time::Instant::now();
+                thread_sx
+                    .send(MsgI::HeartBeat(crate::message_handler::MsgHB::Processes(
+                        process_sampler.sample(),
+                    )))
+                    .ok();
+            }
             thread_sx
                 .send(MsgI::HeartBeat(crate::message_handler::MsgHB::UpdateTimer))
                 .ok();
@@ -11,6 +11,7 @@ use tauri::{Builder, Manager};
 
 use crate::message_handler::MessageHandler;
 
+mod activity;
 mod app_error;
 mod application_state;
 mod check_version;
@@ -42,7 +43,7 @@ async fn main() -> Result<(), ()> {
 
     let (setup_tx, setup_rx) = tokio::sync::oneshot::channel();
     MessageHandler::init(rx, sx.clone(), setup_rx);
-    heartbeat_process(&sx);
+    heartbeat_process(&sx, db::CpuMode::default(), vec![]);
 
     Builder::default()
         .setup(|app| {
@@ -73,20 +74,50 @@ async fn main() -> Result<(), ()> {
                 api.prevent_close();
                 sx2.send(MsgI::Window(MsgWV::Hide)).ok();
             }
-            tauri::WindowEvent::Moved(val) => {
-                if val.x <= -32000 && val.y <= -32000 {
-                    sx2.send(MsgI::Window(MsgWV::Minimize)).ok();
-                }
+            tauri::WindowEvent::Moved(val) if val.x <= -32000 && val.y <= -32000 => {
+                sx2.send(MsgI::Window(MsgWV::Minimize)).ok();
             }
             _ => (),
         })
         .invoke_handler(tauri::generate_handler![
+            request_handlers::activate_profile,
+            request_handlers::create_profile,
+            request_handlers::delete_profile,
+            request_handlers::extend_session,
+            request_handlers::get_activity,
+            request_handlers::get_activity_history,
+            request_handlers::get_alignment,
+            request_handlers::get_cycle,
+            request_handlers::get_flow,
+            request_handlers::get_interruptions,
+            request_handlers::get_process_watches,
+            request_handlers::get_profiles,
+            request_handlers::get_reminders,
+            request_handlers::get_resume_behaviours,
+            request_handlers::get_schedule,
+            request_handlers::get_stats_heatmap,
+            request_handlers::get_stats_totals,
             request_handlers::init,
             request_handlers::minimize,
             request_handlers::open_location,
             request_handlers::pause_after_break,
+            request_handlers::pause_until,
+            request_handlers::postpone_break,
+            request_handlers::ready,
+            request_handlers::record_interruption,
+            request_handlers::rename_profile,
             request_handlers::reset_settings,
+            request_handlers::set_activity,
+            request_handlers::set_alignment,
+            request_handlers::set_cycle,
+            request_handlers::set_flow,
+            request_handlers::set_process_watches,
+            request_handlers::set_reminders,
+            request_handlers::set_resume_behaviour,
+            request_handlers::set_schedule,
             request_handlers::set_settings,
+            request_handlers::skip_break,
+            request_handlers::start_break,
             request_handlers::toggle_pause,
         ])
         .plugin(tauri_plugin_single_instance::init(
@@ -1,8 +1,16 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
-use crate::request_handlers::{CpuMeasure, FrontEndState};
+use crate::{
+    activity::ActivitySample,
+    db::{
+        ActivityHistory, InterruptionType, ModelActivity, ModelAlignment, ModelCycleSegment,
+        ModelFlow, ModelInterruption, ModelProcessWatch, ModelReminder, ModelResumeBehaviour,
+        ModelSchedule, PauseReason, Profiles, StatsHeatmap, StatsPeriod, StatsTotals,
+    },
+    request_handlers::{CpuMeasure, FrontEndState},
+};
 
 /// Get information about self for the Footer component
 /// BUILD_DATE is injected via the build.rs file
@@ -31,43 +39,172 @@ impl Default for PackageInfo {
 #[derive(Debug, Clone)]
 /// Front End Messages
 pub enum MsgFE {
+    Activity(ModelActivity),
+    ActivityHistory(ActivityHistory),
+    Alignment(ModelAlignment),
+    /// The break has ended, and the next work session will start once the user is ready
+    AwaitingReady,
+    /// Seconds left until the break starts, 0 once the break warning has ended
+    BreakWarning(u16),
     Cpu(CpuMeasure),
+    Cycle(Vec<ModelCycleSegment>),
     Error,
+    Flow(ModelFlow),
     GetSettings,
     GoToSettings,
     GoToTimer,
+    Interruptions(Vec<ModelInterruption>),
     NextBreak,
     OnBreak,
     PackageInfo(PackageInfo),
     Paused(bool),
+    ProcessWatches(Vec<ModelProcessWatch>),
+    Profiles(Profiles),
+    /// A reminder is due, the frontend shows it for its `duration_sec`
+    Reminder(ModelReminder),
+    Reminders(Vec<ModelReminder>),
+    ResumeBehaviours(Vec<ModelResumeBehaviour>),
+    Schedule(ModelSchedule),
+    SessionEnded,
     SessionsBeforeLong,
+    StatsHeatmap(Vec<StatsHeatmap>),
+    StatsTotals(StatsTotals),
 }
 
 /// These need to match the frontend types.InvokeMessage const
 impl MsgFE {
     pub const fn as_str(&self) -> &'static str {
         match self {
+            Self::Activity(_) => "get::activity",
+            Self::ActivityHistory(_) => "get::activity-history",
+            Self::Alignment(_) => "get::alignment",
+            Self::AwaitingReady => "awaiting-ready",
+            Self::BreakWarning(_) => "break-warning",
             Self::Cpu(_) => "cpu",
+            Self::Cycle(_) => "get::cycle",
             Self::Error => "error",
+            Self::Flow(_) => "get::flow",
             Self::GetSettings => "get::settings",
             Self::GoToSettings => "goto::settings",
             Self::GoToTimer => "goto::timer",
+            Self::Interruptions(_) => "get::interruptions",
             Self::NextBreak => "next-break",
             Self::OnBreak => "on-break",
             Self::PackageInfo(_) => "package-info",
             Self::Paused(_) => "paused",
+            Self::ProcessWatches(_) => "get::process-watches",
+            Self::Profiles(_) => "get::profiles",
+            Self::Reminder(_) => "reminder",
+            Self::Reminders(_) => "get::reminders",
+            Self::ResumeBehaviours(_) => "get::resume-behaviours",
+            Self::Schedule(_) => "get::schedule",
+            Self::SessionEnded => "session-ended",
             Self::SessionsBeforeLong => "sessions-before-long",
+            Self::StatsHeatmap(_) => "get::stats-heatmap",
+            Self::StatsTotals(_) => "get::stats-totals",
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Activity signal messages
+pub enum MsgAC {
+    Get,
+    Set(ModelActivity),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Clock-aligned session messages
+pub enum MsgAL {
+    Get,
+    Set(ModelAlignment),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 /// Break message
 pub enum MsgB {
     End,
+    /// Postpone the current break by the given number of minutes
+    Postpone(u8),
+    /// Start the next work session, if waiting for the user to be ready after a break
+    Ready,
+    /// Show the break window, if the timer was restored into a break on start
+    Restore,
+    /// End the current break early
+    Skip,
     Start,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Flow mode messages
+pub enum MsgFL {
+    Get,
+    Set(ModelFlow),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Interruption messages, both only apply to the current session
+pub enum MsgIR {
+    Get,
+    /// Log an interruption, with an optional note, against the current work session
+    Record(InterruptionType, Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Profile messages
+pub enum MsgP {
+    /// Copy the profile into the settings, and make it the active profile
+    Activate(i64),
+    /// Create a profile from the current settings, and make it the active profile
+    Create(String),
+    Delete(i64),
+    Get,
+    /// Get the cycle of the active profile
+    GetCycle,
+    Rename(i64, String),
+    /// Replace the cycle of the active profile, an empty cycle uses the `number_session_before_break` pattern
+    SetCycle(Vec<ModelCycleSegment>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Recurring reminder messages
+pub enum MsgRM {
+    Get,
+    /// Replace all reminders
+    Set(Vec<ModelReminder>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Process watch messages
+pub enum MsgPW {
+    Get,
+    /// Replace all process watches
+    Set(Vec<ModelProcessWatch>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Resume behaviour messages
+pub enum MsgRB {
+    Get,
+    Set(ModelResumeBehaviour),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Stats request messages
+pub enum MsgS {
+    /// The activity history, pauses, and sessions, between two unix times
+    ActivityHistory(i64, i64),
+    Heatmap,
+    Totals(StatsPeriod),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Working hours schedule messages
+pub enum MsgWH {
+    Get,
+    Set(ModelSchedule),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 /// Window Visibility Messages
 pub enum MsgWV {
@@ -78,11 +215,38 @@ pub enum MsgWV {
     Toggle,
 }
 
+/// How long a timed pause should last, the timer resumes automatically once it has ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseUntil {
+    Minutes(u16),
+    /// The next occurrence of the given localtime
+    Time {
+        hour: u8,
+        minute: u8,
+    },
+    /// The start of the next working day, or the same time tomorrow if there isn't a working hours schedule
+    NextWorkingDay,
+}
+
+/// A gap between two heartbeats, large enough that the system is assumed to have been suspended, or the wall-clock has jumped forward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Suspension {
+    /// Wall-clock time between the two heartbeats
+    pub wall: Duration,
+    /// How far `Instant` advanced between the two heartbeats, on Linux & macOS this excludes any time spent suspended
+    pub instant: Duration,
+}
+
 #[derive(Debug, Clone)]
 /// Heartbeat Message
 pub enum MsgHB {
     Abort,
-    OnHeartbeat(Option<f32>),
+    /// The activity sample is only taken once a second
+    OnHeartbeat(Option<ActivitySample>),
+    /// Names of every running process
+    Processes(Vec<String>),
+    Suspended(Suspension),
     Update(Arc<tokio::task::JoinHandle<()>>),
     UpdateTimer,
 }
@@ -90,13 +254,29 @@ pub enum MsgHB {
 #[derive(Debug, Clone)]
 /// InternalMessage
 pub enum MsgI {
+    Activity(MsgAC),
+    Alignment(MsgAL),
     Break(MsgB),
+    /// Add the given number of minutes to the current work session
+    Extend(u8),
+    Flow(MsgFL),
     HeartBeat(MsgHB),
+    Interruption(MsgIR),
     OpenLocation(Option<String>),
-    Pause,
+    /// Toggle the pause status, the reason is only used when pausing, used by the systemtray & frontend pause buttons
+    Pause(PauseReason),
+    PauseUntil(PauseUntil),
+    ProcessWatch(MsgPW),
+    Profile(MsgP),
+    Reminder(MsgRM),
     ResetSettings,
     ResetTimer,
+    ResumeBehaviour(MsgRB),
+    Schedule(MsgWH),
+    /// Pause, or resume, the timer, ignored if already in that state, a resume only ends a pause with the same reason
+    SetPaused(bool, PauseReason),
     SetSetting(FrontEndState),
+    Stats(MsgS),
     ToFrontEnd(MsgFE),
     UpdateMenuTimer,
     UpdatePause(bool),
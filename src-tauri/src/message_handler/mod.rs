@@ -7,7 +7,7 @@ use tracing_subscriber::{fmt as t_fmt, prelude::__tracing_subscriber_SubscriberE
 use crate::{
     app_error::AppError,
     application_state::ApplicationState,
-    db::{self, ModelSettings},
+    db::{self, ModelProfile, ModelSettings, ModelTimerState},
 };
 
 mod messages;
@@ -72,24 +72,61 @@ impl MessageHandler {
             MsgHB::Update(handle) => {
                 state.heartbeat_update(handle);
             }
-            MsgHB::OnHeartbeat(cpu_usage) => {
-                state.on_heartbeat(cpu_usage);
+            MsgHB::OnHeartbeat(activity) => {
+                state.on_heartbeat(activity);
+            }
+            MsgHB::Processes(names) => {
+                state.handle_processes(&names);
+            }
+            MsgHB::Suspended(suspension) => {
+                state.handle_suspension(suspension);
             }
             MsgHB::UpdateTimer => {
                 state.update_timer_check();
             }
         }
     }
+    /// Log the error of a message handler, and notify the frontend
+    fn handle_result(result: Result<(), AppError>, state: &ApplicationState) {
+        if let Err(e) = result {
+            tracing::error!("{:#?}", e);
+            state.send(MsgI::ToFrontEnd(MsgFE::Error));
+        }
+    }
+
     /// Start the message handling loop in it's own tokio thread
     pub async fn start_message_loop(mut state: ApplicationState, mut rx: Receiver<MsgI>) {
         while let Ok(msg) = rx.recv().await {
             match msg {
+                MsgI::Activity(activity_message) => {
+                    Self::handle_result(state.handle_activity(activity_message).await, &state);
+                }
+
+                MsgI::Alignment(alignment_message) => {
+                    Self::handle_result(state.handle_alignment(alignment_message).await, &state);
+                }
+
                 MsgI::Break(break_message) => {
-                    state.handle_break(break_message);
+                    state.handle_break(break_message).await;
+                }
+
+                MsgI::Extend(minutes) => {
+                    state.extend_session(minutes);
                 }
 
                 MsgI::HeartBeat(msg_hb) => Self::handle_heartbeat(msg_hb, &mut state),
 
+                MsgI::Flow(flow_message) => {
+                    Self::handle_result(state.handle_flow(flow_message).await, &state);
+                }
+
+                MsgI::Interruption(interruption_message) => {
+                    Self::handle_result(
+                        state.handle_interruption(interruption_message).await,
+                        &state,
+                    );
+                }
+
                 MsgI::OpenLocation(location) => {
                     if let Some(location) = location {
                         open::that(location).ok();
@@ -98,18 +135,34 @@ impl MessageHandler {
                     }
                 }
 
-                MsgI::Pause => {
-                    let paused = state.toggle_pause();
-                    state.update_menu_pause(paused);
-                    state.update_icon(paused);
-                    state.send(MsgI::ToFrontEnd(MsgFE::Paused(paused)));
+                MsgI::Pause(reason) => {
+                    let paused = state.toggle_pause(reason);
+                    state.update_paused(paused);
                 }
 
-                MsgI::ResetSettings => {
-                    if let Err(e) = state.reset_settings().await {
-                        tracing::error!("{:#?}", e);
-                        state.send(MsgI::ToFrontEnd(MsgFE::Error));
+                MsgI::PauseUntil(until) => {
+                    if state.pause_until(until) {
+                        state.update_paused(true);
                     }
+                }
+
+                MsgI::ProcessWatch(process_watch_message) => {
+                    Self::handle_result(
+                        state.handle_process_watch(process_watch_message).await,
+                        &state,
+                    );
+                }
+
+                MsgI::Profile(profile_message) => {
+                    Self::handle_result(state.handle_profile(profile_message).await, &state);
+                }
+
+                MsgI::Reminder(reminder_message) => {
+                    Self::handle_result(state.handle_reminder(reminder_message).await, &state);
+                }
+
+                MsgI::ResetSettings => {
+                    Self::handle_result(state.reset_settings().await, &state);
                     state.update_menu_all();
                 }
 
@@ -117,14 +170,32 @@ impl MessageHandler {
                     state.reset_timer();
                 }
 
+                MsgI::ResumeBehaviour(resume_behaviour_message) => {
+                    Self::handle_result(
+                        state
+                            .handle_resume_behaviour(resume_behaviour_message)
+                            .await,
+                        &state,
+                    );
+                }
+
+                MsgI::Schedule(schedule_message) => {
+                    Self::handle_result(state.handle_schedule(schedule_message).await, &state);
+                }
+
+                MsgI::SetPaused(paused, reason) => {
+                    state.set_paused(paused, reason);
+                }
+
                 MsgI::SetSetting(frontend_state) => {
-                    if let Err(e) = state.update_settings(frontend_state).await {
-                        tracing::error!("{:#?}", e);
-                        state.send(MsgI::ToFrontEnd(MsgFE::Error));
-                    }
+                    Self::handle_result(state.update_settings(frontend_state).await, &state);
                     state.update_menu_all();
                 }
 
+                MsgI::Stats(stats_message) => {
+                    Self::handle_result(state.handle_stats(stats_message).await, &state);
+                }
+
                 MsgI::ToFrontEnd(to_front_end) => {
                     state.emit_to_frontend(to_front_end);
                 }
@@ -134,7 +205,7 @@ impl MessageHandler {
                     state.update_pause_after_break(pause);
                 }
                 MsgI::Window(window_visibility) => {
-                    state.handle_visibility(window_visibility);
+                    state.handle_visibility(window_visibility).await;
                 }
             }
         }
@@ -163,16 +234,25 @@ impl MessageHandler {
                 std::process::exit(1)
             }
 
-            let Ok(sqlite) = db::init_db(&data_location).await else {
-                println!("init_db err");
-                std::process::exit(1)
+            let sqlite = match db::init_db(&data_location).await {
+                Ok(sqlite) => sqlite,
+                Err(e) => {
+                    println!("init_db err: {e}");
+                    tracing::error!("{:#?}", e);
+                    std::process::exit(1)
+                }
             };
             let Ok(settings) = ModelSettings::init(&sqlite).await else {
                 println!("ModelSettings::init err");
                 std::process::exit(1)
             };
+            if let Err(e) = ModelProfile::init(&sqlite, &settings).await {
+                tracing::error!("{:#?}", e);
+            }
+
+            let timer_state = ModelTimerState::get(&sqlite).await.unwrap_or_default();
 
-            let state = ApplicationState::new(
+            let mut state = ApplicationState::new(
                 app_handle,
                 data_location,
                 sx,
@@ -180,6 +260,37 @@ impl MessageHandler {
                 sqlite,
                 system_tray_menu,
             );
+            if let Err(e) = state.update_cycle().await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_schedule(MsgWH::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_resume_behaviour(MsgRB::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_flow(MsgFL::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_alignment(MsgAL::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_activity(MsgAC::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_reminder(MsgRM::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.handle_process_watch(MsgPW::Get).await {
+                tracing::error!("{:#?}", e);
+            }
+            if let Err(e) = state.restore_active_use().await {
+                tracing::error!("{:#?}", e);
+            }
+            state.restore_timer_state(timer_state);
+            if let Err(e) = state.update_profiles().await {
+                tracing::error!("{:#?}", e);
+            }
             Self::start_message_loop(state, rx).await;
         });
     }
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::db::{CpuMode, PauseReason, SuspendPolicy};
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct CpuMeasure {
+    /// How every value was measured
+    pub mode: CpuMode,
     pub current: f32,
     pub pause: Option<f32>,
     pub resume: Option<f32>,
@@ -17,23 +21,64 @@ pub struct FrontEndState {
     pub auto_resume_threshold: u8,
     pub auto_resume_timespan_sec: u16,
     pub auto_resume: bool,
+    pub break_warning_sec: u16,
+    pub carry_over_skipped: bool,
+    pub daily_cap_sec: u32,
     pub fullscreen: bool,
     pub long_break_as_sec: u16,
     pub number_session_before_break: u8,
+    pub overwork_limit_sec: u16,
     pub paused: bool,
+    /// Why the timer is paused, only sent to the frontend
+    pub pause_reason: Option<PauseReason>,
+    pub postpone_limit_per_day: u8,
+    pub ready_after_break: bool,
+    pub restore_max_age_sec: u32,
     pub session_as_sec: u16,
     pub short_break_as_sec: u16,
     pub start_on_boot: bool,
+    pub suspend_policy: SuspendPolicy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct ShowTimer {
     interval: u16,
+    postpones_remaining: u8,
+    /// How long the break is postponed for, matches the systemtray `Postpone` item
+    postpone_minutes: u8,
     strategy: String,
 }
 
 impl ShowTimer {
-    pub const fn new(interval: u16, strategy: String) -> Self {
-        Self { interval, strategy }
+    pub const fn new(
+        interval: u16,
+        postpones_remaining: u8,
+        postpone_minutes: u8,
+        strategy: String,
+    ) -> Self {
+        Self {
+            interval,
+            postpones_remaining,
+            postpone_minutes,
+            strategy,
+        }
+    }
+}
+
+/// This needs to match frontend types.ShowWarning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ShowWarning {
+    /// Seconds left until the break starts, 0 once the break warning has ended
+    left: u16,
+    /// How long the break is delayed for, matches the systemtray `Delay` item, 0 whilst clock-aligned, as the break can't be delayed
+    delay_minutes: u8,
+}
+
+impl ShowWarning {
+    pub const fn new(left: u16, delay_minutes: u8) -> Self {
+        Self {
+            left,
+            delay_minutes,
+        }
     }
 }
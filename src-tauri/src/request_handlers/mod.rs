@@ -1,6 +1,14 @@
 use crate::{
     TauriState, check_version,
-    message_handler::{MsgFE, MsgI, MsgWV, PackageInfo},
+    db::{
+        InterruptionType, ModelActivity, ModelAlignment, ModelCycleSegment, ModelFlow,
+        ModelProcessWatch, ModelReminder, ModelResumeBehaviour, ModelSchedule, PauseReason,
+        StatsPeriod,
+    },
+    message_handler::{
+        MsgAC, MsgAL, MsgB, MsgFE, MsgFL, MsgI, MsgIR, MsgP, MsgPW, MsgRB, MsgRM, MsgS, MsgWH,
+        MsgWV, PackageInfo, PauseUntil,
+    },
 };
 
 mod messages;
@@ -19,6 +27,7 @@ pub fn init(sx: TauriState<'_>) {
     ] {
         sx.send(MsgI::ToFrontEnd(message)).ok();
     }
+    sx.send(MsgI::Break(MsgB::Restore)).ok();
     check_version::fetch_updates(sx.inner().clone());
 }
 
@@ -29,11 +38,38 @@ pub fn reset_settings(sx: TauriState<'_>) {
     sx.send(MsgI::ResetSettings).ok();
 }
 
-/// Toggle the pause option
+/// Toggle the pause option, the reason, which defaults to manual, is only used when pausing
 #[tauri::command]
 #[expect(clippy::needless_pass_by_value)]
-pub fn toggle_pause(sx: TauriState<'_>) {
-    sx.send(MsgI::Pause).ok();
+pub fn toggle_pause(sx: TauriState<'_>, reason: Option<PauseReason>) {
+    sx.send(MsgI::Pause(reason.unwrap_or(PauseReason::Manual)))
+        .ok();
+}
+
+/// Pause the timer, it will automatically resume once the pause has ended
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn pause_until(sx: TauriState<'_>, until: PauseUntil) {
+    sx.send(MsgI::PauseUntil(until)).ok();
+}
+
+/// Log an interruption, with an optional note, against the current work session
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn record_interruption(
+    sx: TauriState<'_>,
+    interruption_type: InterruptionType,
+    note: Option<String>,
+) {
+    sx.send(MsgI::Interruption(MsgIR::Record(interruption_type, note)))
+        .ok();
+}
+
+/// Request the interruptions of the current session
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_interruptions(sx: TauriState<'_>) {
+    sx.send(MsgI::Interruption(MsgIR::Get)).ok();
 }
 
 /// Set the pause after break setting
@@ -64,3 +100,207 @@ pub fn set_settings(sx: TauriState<'_>, value: FrontEndState) {
 pub fn minimize(sx: TauriState<'_>) {
     sx.send(MsgI::Window(MsgWV::Toggle)).ok();
 }
+
+/// Request the focus, session, and break totals, grouped by day, week, or month
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_stats_totals(sx: TauriState<'_>, period: StatsPeriod) {
+    sx.send(MsgI::Stats(MsgS::Totals(period))).ok();
+}
+
+/// Request the per-minute activity history, along with the pauses & sessions, between two unix times
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_activity_history(sx: TauriState<'_>, from: i64, to: i64) {
+    sx.send(MsgI::Stats(MsgS::ActivityHistory(from, to))).ok();
+}
+
+/// Request the weekday & hour of day focus heatmap
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_stats_heatmap(sx: TauriState<'_>) {
+    sx.send(MsgI::Stats(MsgS::Heatmap)).ok();
+}
+
+/// Skip the current break
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn skip_break(sx: TauriState<'_>) {
+    sx.send(MsgI::Break(MsgB::Skip)).ok();
+}
+
+/// Postpone the current break by the given number of minutes
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn postpone_break(sx: TauriState<'_>, minutes: u8) {
+    sx.send(MsgI::Break(MsgB::Postpone(minutes))).ok();
+}
+
+/// Add the given number of minutes to the current work session
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn extend_session(sx: TauriState<'_>, minutes: u8) {
+    sx.send(MsgI::Extend(minutes)).ok();
+}
+
+/// Start the next work session, once a break has ended, and the app is waiting for the user to be ready
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn ready(sx: TauriState<'_>) {
+    sx.send(MsgI::Break(MsgB::Ready)).ok();
+}
+
+/// Start a break now, rather than waiting for the current work session to end
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn start_break(sx: TauriState<'_>) {
+    sx.send(MsgI::Break(MsgB::Start)).ok();
+}
+
+/// Request all profiles, and the id of the active profile
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_profiles(sx: TauriState<'_>) {
+    sx.send(MsgI::Profile(MsgP::Get)).ok();
+}
+
+/// Create a profile from the current settings, and make it the active profile
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn create_profile(sx: TauriState<'_>, name: String) {
+    sx.send(MsgI::Profile(MsgP::Create(name))).ok();
+}
+
+/// Rename a user profile
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn rename_profile(sx: TauriState<'_>, profile_id: i64, name: String) {
+    sx.send(MsgI::Profile(MsgP::Rename(profile_id, name))).ok();
+}
+
+/// Delete a user profile, the active profile can't be deleted
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn delete_profile(sx: TauriState<'_>, profile_id: i64) {
+    sx.send(MsgI::Profile(MsgP::Delete(profile_id))).ok();
+}
+
+/// Switch to the given profile
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn activate_profile(sx: TauriState<'_>, profile_id: i64) {
+    sx.send(MsgI::Profile(MsgP::Activate(profile_id))).ok();
+}
+
+/// Request the activity signal options
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_activity(sx: TauriState<'_>) {
+    sx.send(MsgI::Activity(MsgAC::Get)).ok();
+}
+
+/// Set the activity signal options, the signal that is used to auto-pause & auto-resume
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_activity(sx: TauriState<'_>, activity: ModelActivity) {
+    sx.send(MsgI::Activity(MsgAC::Set(activity))).ok();
+}
+
+/// Request the clock-aligned session options
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_alignment(sx: TauriState<'_>) {
+    sx.send(MsgI::Alignment(MsgAL::Get)).ok();
+}
+
+/// Set the clock-aligned session options, whilst enabled sessions end at local wall-clock boundaries
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_alignment(sx: TauriState<'_>, alignment: ModelAlignment) {
+    sx.send(MsgI::Alignment(MsgAL::Set(alignment))).ok();
+}
+
+/// Request the flow mode options
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_flow(sx: TauriState<'_>) {
+    sx.send(MsgI::Flow(MsgFL::Get)).ok();
+}
+
+/// Set the flow mode options, whilst enabled work sessions count up until a break is started
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_flow(sx: TauriState<'_>, flow: ModelFlow) {
+    sx.send(MsgI::Flow(MsgFL::Set(flow))).ok();
+}
+
+/// Request the cycle of the active profile
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_cycle(sx: TauriState<'_>) {
+    sx.send(MsgI::Profile(MsgP::GetCycle)).ok();
+}
+
+/// Set the cycle of the active profile, an empty cycle removes the custom cycle
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_cycle(sx: TauriState<'_>, segments: Vec<ModelCycleSegment>) {
+    sx.send(MsgI::Profile(MsgP::SetCycle(segments))).ok();
+}
+
+/// Request the working hours schedule
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_schedule(sx: TauriState<'_>) {
+    sx.send(MsgI::Schedule(MsgWH::Get)).ok();
+}
+
+/// Replace the working hours schedule, a schedule without any windows is disabled
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_schedule(sx: TauriState<'_>, schedule: ModelSchedule) {
+    sx.send(MsgI::Schedule(MsgWH::Set(schedule))).ok();
+}
+
+/// Request all process watches
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_process_watches(sx: TauriState<'_>) {
+    sx.send(MsgI::ProcessWatch(MsgPW::Get)).ok();
+}
+
+/// Replace all process watches, the timer is paused whilst any enabled watch matches a running process
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_process_watches(sx: TauriState<'_>, process_watches: Vec<ModelProcessWatch>) {
+    sx.send(MsgI::ProcessWatch(MsgPW::Set(process_watches)))
+        .ok();
+}
+
+/// Request all recurring reminders
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_reminders(sx: TauriState<'_>) {
+    sx.send(MsgI::Reminder(MsgRM::Get)).ok();
+}
+
+/// Replace all recurring reminders
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_reminders(sx: TauriState<'_>, reminders: Vec<ModelReminder>) {
+    sx.send(MsgI::Reminder(MsgRM::Set(reminders))).ok();
+}
+
+/// Request the resume behaviour of every pause reason
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn get_resume_behaviours(sx: TauriState<'_>) {
+    sx.send(MsgI::ResumeBehaviour(MsgRB::Get)).ok();
+}
+
+/// Set what happens to the current session when the timer is resumed after a pause with the given reason
+#[tauri::command]
+#[expect(clippy::needless_pass_by_value)]
+pub fn set_resume_behaviour(sx: TauriState<'_>, behaviour: ModelResumeBehaviour) {
+    sx.send(MsgI::ResumeBehaviour(MsgRB::Set(behaviour))).ok();
+}
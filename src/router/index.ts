@@ -1,7 +1,9 @@
 import { createRouter, createWebHistory } from 'vue-router';
 import { FrontEndNames, FrontEndRoutes } from '../types';
+import Reminder from '../Views/ReminderView.vue';
 import Settings from '../Views/SettingsView.vue';
 import Timer from '../Views/TimerView.vue';
+import Warning from '../Views/WarningView.vue';
 
 const router = createRouter({
 	history: createWebHistory('/'),
@@ -11,10 +13,20 @@ const router = createRouter({
 			name: FrontEndNames.Settings,
 			component: Settings
 		},
+		{
+			path: FrontEndRoutes.Reminder,
+			name: FrontEndNames.Reminder,
+			component: Reminder
+		},
 		{
 			path: FrontEndRoutes.Timer,
 			name: FrontEndNames.Timer,
 			component: Timer
+		},
+		{
+			path: FrontEndRoutes.Warning,
+			name: FrontEndNames.Warning,
+			component: Warning
 		}
 	]
 
@@ -1,9 +1,10 @@
 import { defineStore } from 'pinia';
-import { CpuMeasure, ModuleName } from '../types';
+import { CpuMeasure, CpuMode, ModuleName } from '../types';
 
 export const cpuUsageModule = defineStore(ModuleName.CpuUsage, {
 
 	state: () => ({
+		mode: CpuMode.Global as CpuMode,
 		current: 0,
 		average_pause: 0,
 		average_resume: 0
@@ -11,6 +12,7 @@ export const cpuUsageModule = defineStore(ModuleName.CpuUsage, {
 	actions: {
 
 		set_all (x: CpuMeasure): void {
+			this.mode = x.mode;
 			this.current = x.current;
 			this.average_pause = x.pause ?? 0;
 			this.average_resume = x.resume ?? 0;
@@ -4,19 +4,39 @@ import { BreakTypes, ModuleName } from '../types';
 export const intervalModule = defineStore(ModuleName.Interval, {
 
 	state: () => ({
+		awaiting_ready: false,
 		interval: 0,
 		original_interval: 0,
 		break_type: BreakTypes.Short as BreakTypes,
+		break_warning: 0,
+		delay_minutes: 0,
+		postpone_minutes: 0,
+		postpones_remaining: 0,
 		strategy: ''
 	}),
 	actions: {
 
+		set_awaiting_ready (x: boolean): void {
+			this.awaiting_ready = x;
+		},
 		decrement () {
 			this.interval--;
 		},
+		set_break_warning (x: number): void {
+			this.break_warning = x;
+		},
+		set_delay_minutes (x: number): void {
+			this.delay_minutes = x;
+		},
 		set_interval (x: number): void {
 			this.interval = x;
 		},
+		set_postpone_minutes (x: number): void {
+			this.postpone_minutes = x;
+		},
+		set_postpones_remaining (x: number): void {
+			this.postpones_remaining = x;
+		},
 		set_strategy (x: string): void {
 			this.strategy = x;
 		},
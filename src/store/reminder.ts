@@ -0,0 +1,13 @@
+import { defineStore } from 'pinia';
+import { ModuleName, Reminder } from '../types';
+
+export const reminderModule = defineStore(ModuleName.Reminder, {
+
+	state: () => ({ reminder: null as Reminder | null }),
+
+	actions: {
+		set_reminder (x: Reminder): void {
+			this.reminder = x;
+		}
+	}
+});
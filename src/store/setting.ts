@@ -1,17 +1,26 @@
 import { defineStore } from 'pinia';
-import { FrontEndState, ModuleName } from '../types';
+import { FrontEndState, ModuleName, PauseReason, SuspendPolicy } from '../types';
 
 export const settingModule = defineStore(ModuleName.Setting, {
 
 	state: () => ({
+		break_warning_sec: 0,
+		carry_over_skipped: false,
+		daily_cap_sec: 0,
 		start_on_boot: false,
 		fullscreen: false,
 		long_break_as_sec: 0,
 		number_session_before_break: 0,
+		overwork_limit_sec: 0,
 		paused: false,
+		pause_reason: null as PauseReason | null,
+		postpone_limit_per_day: 0,
+		ready_after_break: false,
+		restore_max_age_sec: 0,
 		session_as_sec: 0,
 		session_before_next_long_break: '',
 		short_break_as_sec: 0,
+		suspend_policy: SuspendPolicy.Resume as SuspendPolicy,
 		auto_pause: false,
 		auto_resume: false,
 		auto_pause_threshold: 0,
@@ -29,13 +38,22 @@ export const settingModule = defineStore(ModuleName.Setting, {
 				auto_resume_threshold: this.auto_resume_threshold,
 				auto_resume_timespan_sec: this.auto_resume_timespan_sec,
 				auto_resume: this.auto_resume,
+				break_warning_sec: this.break_warning_sec,
+				carry_over_skipped: this.carry_over_skipped,
+				daily_cap_sec: this.daily_cap_sec,
 				fullscreen: this.fullscreen,
 				long_break_as_sec: this.long_break_as_sec,
 				number_session_before_break: this.number_session_before_break,
+				overwork_limit_sec: this.overwork_limit_sec,
 				paused: this.paused,
+				pause_reason: this.pause_reason,
+				postpone_limit_per_day: this.postpone_limit_per_day,
+				ready_after_break: this.ready_after_break,
+				restore_max_age_sec: this.restore_max_age_sec,
 				session_as_sec: this.session_as_sec,
 				short_break_as_sec: this.short_break_as_sec,
-				start_on_boot: this.start_on_boot
+				start_on_boot: this.start_on_boot,
+				suspend_policy: this.suspend_policy
 			};
 		}
 	},
@@ -69,6 +87,18 @@ export const settingModule = defineStore(ModuleName.Setting, {
 			this.auto_resume_timespan_sec = x;
 		},
 
+		set_break_warning_sec (x: number): void {
+			this.break_warning_sec = x;
+		},
+
+		set_carry_over_skipped (x: boolean): void {
+			this.carry_over_skipped = x;
+		},
+
+		set_daily_cap_sec (x: number): void {
+			this.daily_cap_sec = x;
+		},
+
 		set_fullscreen (x: boolean): void {
 			this.fullscreen = x;
 		},
@@ -84,20 +114,44 @@ export const settingModule = defineStore(ModuleName.Setting, {
 		set_number_session_before_break (x: number): void {
 			this.number_session_before_break = x;
 		},
+		set_overwork_limit_sec (x: number): void {
+			this.overwork_limit_sec = x;
+		},
 		set_paused (x: boolean): void {
 			this.paused = x;
 		},
+		set_postpone_limit_per_day (x: number): void {
+			this.postpone_limit_per_day = x;
+		},
+		set_ready_after_break (x: boolean): void {
+			this.ready_after_break = x;
+		},
+		set_restore_max_age_sec (x: number): void {
+			this.restore_max_age_sec = x;
+		},
+		set_suspend_policy (x: SuspendPolicy): void {
+			this.suspend_policy = x;
+		},
 		set_session_before_next_long_break (x: string): void {
 			this.session_before_next_long_break = x;
 		},
 		set_current_state (x: FrontEndState): void {
 			this.start_on_boot = x.start_on_boot;
+			this.break_warning_sec = x.break_warning_sec;
+			this.carry_over_skipped = x.carry_over_skipped;
+			this.daily_cap_sec = x.daily_cap_sec;
 			this.fullscreen = x.fullscreen;
 			this.long_break_as_sec = x.long_break_as_sec;
 			this.number_session_before_break = x.number_session_before_break;
+			this.overwork_limit_sec = x.overwork_limit_sec;
 			this.paused = x.paused;
+			this.pause_reason = x.pause_reason;
+			this.postpone_limit_per_day = x.postpone_limit_per_day;
+			this.ready_after_break = x.ready_after_break;
+			this.restore_max_age_sec = x.restore_max_age_sec;
 			this.session_as_sec = x.session_as_sec;
 			this.short_break_as_sec = x.short_break_as_sec;
+			this.suspend_policy = x.suspend_policy;
 			this.auto_pause = x.auto_pause;
 			this.auto_resume = x.auto_resume;
 			this.auto_pause_threshold = x.auto_pause_threshold;
@@ -1,26 +1,62 @@
 export type ConstT<T> = T[keyof T];
 
 export const InvokeMessage = {
+	ActivateProfile: 'activate_profile',
+	CreateProfile: 'create_profile',
+	DeleteProfile: 'delete_profile',
+	ExtendSession: 'extend_session',
+	GetActivity: 'get_activity',
+	GetActivityHistory: 'get_activity_history',
+	GetAlignment: 'get_alignment',
+	GetCycle: 'get_cycle',
+	GetFlow: 'get_flow',
+	GetInterruptions: 'get_interruptions',
 	GetPackageInfo: 'get_package_info',
+	GetProcessWatches: 'get_process_watches',
+	GetProfiles: 'get_profiles',
+	GetReminders: 'get_reminders',
+	GetResumeBehaviours: 'get_resume_behaviours',
+	GetSchedule: 'get_schedule',
+	GetStatsHeatmap: 'get_stats_heatmap',
+	GetStatsTotals: 'get_stats_totals',
 	Init: 'init',
 	Minimize: 'minimize',
 	OpenLocation: 'open_location',
 	PauseAfterBreak: 'pause_after_break',
+	PauseUntil: 'pause_until',
+	PostponeBreak: 'postpone_break',
+	Ready: 'ready',
+	RecordInterruption: 'record_interruption',
+	RenameProfile: 'rename_profile',
 	ResetSettings: 'reset_settings',
+	SetActivity: 'set_activity',
+	SetAlignment: 'set_alignment',
+	SetCycle: 'set_cycle',
+	SetFlow: 'set_flow',
+	SetProcessWatches: 'set_process_watches',
+	SetReminders: 'set_reminders',
+	SetResumeBehaviour: 'set_resume_behaviour',
+	SetSchedule: 'set_schedule',
 	SetSettings: 'set_settings',
 	ShowSettings: 'show_settings',
+	SkipBreak: 'skip_break',
+	StartBreak: 'start_break',
 	TogglePause: 'toggle_pause'
 } as const;
 export type InvokeMessage = ConstT<typeof InvokeMessage>;
 
 export const FrontEndRoutes = {
 	Settings: '/',
-	Timer: '/timer'
+	Reminder: '/reminder',
+	Timer: '/timer',
+	Warning: '/warning'
 } as const;
 
 export const FrontEndNames = {
 	Settings: 'settings',
-	Timer: 'timer'
+	Reminder: 'reminder',
+	Timer: 'timer',
+	Warning: 'warning'
 } as const;
 
 export type FrontEndRoutes = ConstT<typeof FrontEndRoutes>;
@@ -29,6 +65,7 @@ export const ModuleName = {
 	Interval: 'interval',
 	NextBreak: 'nextbreak',
 	PackageInfo: 'packageinfo',
+	Reminder: 'reminder',
 	Setting: 'setting',
 	Snack: 'snack',
 	CpuUsage: 'cpu_usage'
@@ -37,17 +74,34 @@ export type ModuleName = ConstT<typeof ModuleName>;
 
 // / These need to match the enum FrontEnd as_str()
 export const ListenMessage = {
+	Activity: 'get::activity',
+	ActivityHistory: 'get::activity-history',
+	Alignment: 'get::alignment',
+	AwaitingReady: 'awaiting-ready',
+	BreakWarning: 'break-warning',
 	Cpu: 'cpu',
+	Cycle: 'get::cycle',
 	Error: 'error',
+	Flow: 'get::flow',
 	Fullscreen: 'fullscreen',
 	GetSettings: 'get::settings',
 	GoToSettings: 'goto::settings',
 	GoToTimer: 'goto::timer',
+	Interruptions: 'get::interruptions',
 	NextBreak: 'next-break',
 	NumberSessionsBeforeLong: 'sessions-before-long',
 	OnBreak: 'on-break',
 	PackageInfo: 'package-info',
-	Paused: 'paused'
+	Paused: 'paused',
+	ProcessWatches: 'get::process-watches',
+	Profiles: 'get::profiles',
+	Reminder: 'reminder',
+	Reminders: 'get::reminders',
+	ResumeBehaviours: 'get::resume-behaviours',
+	Schedule: 'get::schedule',
+	SessionEnded: 'session-ended',
+	StatsHeatmap: 'get::stats-heatmap',
+	StatsTotals: 'get::stats-totals'
 } as const;
 export type ListenMessage = ConstT<typeof ListenMessage>;
 
@@ -66,23 +120,229 @@ export type TSnack = {
 
 export type ShowTimer = {
 	interval: number;
+	postpones_remaining: number;
+	postpone_minutes: number;
 	strategy: string;
 };
 
+// left is 0 once the break warning has ended, delay_minutes is 0 whilst clock-aligned, as the break can't be delayed
+export type ShowWarning = Record<'left' | 'delay_minutes', number>;
+
 export type CpuMeasure = {
+	mode: CpuMode;
 	current: number;
 	pause?: number;
 	resume?: number;
 };
 export type BuildInfo = Record<'homepage' | 'version' | 'build_date', string> & { github_version?: string };
-export type FrontEndState = Record<'fullscreen' | 'auto_pause' | 'paused' | 'start_on_boot' | 'auto_resume', boolean> &
+export const SuspendPolicy = {
+	BreakTaken: 'break_taken',
+	Restart: 'restart',
+	Resume: 'resume'
+} as const;
+export type SuspendPolicy = ConstT<typeof SuspendPolicy>;
+
+export type FrontEndState = { suspend_policy: SuspendPolicy; pause_reason: PauseReason | null } & Record<'fullscreen' | 'auto_pause' | 'paused' | 'start_on_boot' | 'auto_resume' | 'carry_over_skipped' | 'ready_after_break', boolean> &
   Record<
     'auto_pause_threshold' |
     'auto_pause_timespan_sec' |
     'auto_resume_threshold' |
     'auto_resume_timespan_sec' |
+    'break_warning_sec' |
+    'daily_cap_sec' |
     'long_break_as_sec' |
     'number_session_before_break' |
+    'overwork_limit_sec' |
+    'postpone_limit_per_day' |
+    'restore_max_age_sec' |
     'session_as_sec' |
     'short_break_as_sec', number>;
 
+
+export const StatsPeriod = {
+	Day: 'day',
+	Week: 'week',
+	Month: 'month'
+} as const;
+export type StatsPeriod = ConstT<typeof StatsPeriod>;
+
+export type StatsTotal = { period: string } & Record<'focus_sec' | 'sessions_completed' | 'short_breaks' | 'long_breaks' | 'breaks_skipped' | 'breaks_postponed' | 'interruptions', number>;
+export type StatsTotals = {
+	period: StatsPeriod;
+	totals: Array<StatsTotal>;
+};
+export type StatsHeatmap = Record<'weekday' | 'hour' | 'focus_sec' | 'sessions', number>;
+
+export type Profile = {
+	profile_id: number;
+	name: string;
+	built_in: boolean;
+	auto_pause: boolean;
+	auto_pause_threshold: number;
+	auto_pause_timespan_sec: number;
+	auto_resume: boolean;
+	auto_resume_threshold: number;
+	auto_resume_timespan_sec: number;
+	long_break_as_sec: number;
+	number_session_before_break: number;
+	session_as_sec: number;
+	short_break_as_sec: number;
+};
+
+export const SessionType = {
+	Work: 'work',
+	ShortBreak: 'short_break',
+	LongBreak: 'long_break'
+} as const;
+export type SessionType = ConstT<typeof SessionType>;
+
+// Custom cycles alternate between work and break segments, starting with a work segment
+export type CycleSegment = {
+	segment_type: SessionType;
+	duration_sec: number;
+	label: string | null;
+};
+
+// The timer resumes automatically once a timed pause has ended
+export type PauseUntil = { minutes: number } | { time: { hour: number; minute: number } } | 'next_working_day';
+
+// weekday 0 is Sunday, minutes are since the start of the day, end_minute is exclusive
+export type ScheduleWindow = Record<'weekday' | 'start_minute' | 'end_minute', number>;
+
+// date is YYYY-MM-DD
+export type ScheduleException = {
+	date: string;
+	label: string | null;
+};
+
+// A schedule without any windows is disabled
+export type Schedule = {
+	windows: Array<ScheduleWindow>;
+	exceptions: Array<ScheduleException>;
+};
+
+// suspend is only used in the pause history
+export const PauseReason = {
+	Manual: 'manual',
+	Cpu: 'cpu',
+	Schedule: 'schedule',
+	Meeting: 'meeting',
+	AfterBreak: 'after_break',
+	EndOfDay: 'end_of_day',
+	Process: 'process',
+	Suspend: 'suspend'
+} as const;
+export type PauseReason = ConstT<typeof PauseReason>;
+
+export const ResumeBehaviour = {
+	Continue: 'continue',
+	Restart: 'restart',
+	Discard: 'discard'
+} as const;
+export type ResumeBehaviour = ConstT<typeof ResumeBehaviour>;
+
+// What happens to the current session when the timer is resumed after a pause with the given reason
+export type ModelResumeBehaviour = {
+	pause_reason: PauseReason;
+	resume_behaviour: ResumeBehaviour;
+};
+
+export const InterruptionType = {
+	Internal: 'internal',
+	External: 'external'
+} as const;
+export type InterruptionType = ConstT<typeof InterruptionType>;
+
+// session_started_at identifies the work session the interruption was logged against
+export type Interruption = {
+	session_started_at: number;
+	interruption_type: InterruptionType;
+	note: string | null;
+	created_at: number;
+};
+
+export const SignalSource = {
+	Cpu: 'cpu',
+	Network: 'network',
+	Disk: 'disk',
+	Any: 'any',
+	All: 'all'
+} as const;
+export type SignalSource = ConstT<typeof SignalSource>;
+
+// processes sums the usage of every process in cpu_processes, each as a percentage of a single core
+export const CpuMode = {
+	Global: 'global',
+	MaxCore: 'max_core',
+	Processes: 'processes'
+} as const;
+export type CpuMode = ConstT<typeof CpuMode>;
+
+export const Aggregation = {
+	Mean: 'mean',
+	Median: 'median',
+	Percentile: 'percentile',
+	Ewma: 'ewma'
+} as const;
+export type Aggregation = ConstT<typeof Aggregation>;
+
+// The signal used to auto-pause & auto-resume, thresholds are in KB/s, a threshold of 0 excludes the signal from any & all
+// After an auto transition, reversing it needs the cpu usage to pass its threshold by hysteresis_band, and no auto transition happens within dwell_sec of another
+export type Activity = { source: SignalSource; cpu_mode: CpuMode; cpu_processes: Array<string>; aggregation: Aggregation } & Record<'network_threshold_kb' | 'network_timespan_sec' | 'disk_threshold_kb' | 'disk_timespan_sec' | 'percentile' | 'ewma_alpha_percent' | 'hysteresis_band' | 'dwell_sec' | 'history_retention_days', number>;
+
+export const SessionOutcome = {
+	Completed: 'completed',
+	Paused: 'paused',
+	CutShort: 'cut_short',
+	Interrupted: 'interrupted',
+	Natural: 'natural'
+} as const;
+export type SessionOutcome = ConstT<typeof SessionOutcome>;
+
+export type Session = { session_type: SessionType; outcome: SessionOutcome } & Record<'started_at' | 'ended_at' | 'planned_sec' | 'actual_sec', number>;
+
+export type Pause = { pause_reason: PauseReason; resume_behaviour: ResumeBehaviour | null } & Record<'started_at' | 'duration_sec', number>;
+
+// minute is the unix time at the start of the minute, a signal without any samples during the minute is null
+export type ActivityMinute = { minute: number; cpu_mode: CpuMode } & Record<'cpu' | 'network_kb' | 'disk_kb', number | null>;
+
+// Every minute, pause, and session that overlaps the range from - to, both unix times
+export type ActivityHistory = {
+	from: number;
+	to: number;
+	minutes: Array<ActivityMinute>;
+	pauses: Array<Pause>;
+	sessions: Array<Session>;
+};
+
+// Whilst enabled, each period, counted from local midnight, is work followed by a break at its end, period_minutes must divide a day exactly
+export type Alignment = { enabled: boolean } & Record<'period_minutes' | 'break_minutes', number>;
+
+// Whilst enabled, work sessions count up until a break is started, and the break lasts for break_percent of the work done
+export type Flow = { enabled: boolean } & Record<'break_percent' | 'min_break_sec' | 'max_break_sec', number>;
+
+// A plain pattern matches a process name exactly, ignoring case and any .exe suffix, otherwise the pattern is a regex
+export type ProcessWatch = {
+	pattern: string;
+	is_regex: boolean;
+	enabled: boolean;
+};
+
+export const ReminderDisplay = {
+	Notification: 'notification',
+	Overlay: 'overlay'
+} as const;
+export type ReminderDisplay = ConstT<typeof ReminderDisplay>;
+
+// Minutes are since the start of the day, end_minute is exclusive, duration_sec is how long the reminder is shown for
+export type Reminder = {
+	label: string;
+	display_mode: ReminderDisplay;
+	pause_with_timer: boolean;
+	enabled: boolean;
+} & Record<'interval_sec' | 'duration_sec' | 'start_minute' | 'end_minute', number>;
+
+export type Profiles = {
+	active_profile_id: number | null;
+	profiles: Array<Profile>;
+};